@@ -0,0 +1,214 @@
+//! Known answers for the real puzzle inputs, read from `answers/2024.toml`.
+//!
+//! Only the subset of TOML that file needs is understood: `[dayN]` tables for
+//! the default inputs and `[dayN.name]` tables for named ones, holding
+//! `partN = "answer"` keys, with integers allowed unquoted and `#` comments on
+//! their own lines.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs, io,
+    path::Path,
+};
+
+use crate::{
+    errors::ParseInputError,
+    inputs::{self, InputId},
+    parse,
+};
+
+pub const ANSWERS_PATH: &str = "answers/2024.toml";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(InputId, u32), String>,
+    /// Every table, including empty ones.
+    inputs: BTreeSet<InputId>,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, ParseInputError> {
+        let mut answers = HashMap::new();
+        let mut inputs = BTreeSet::new();
+        let mut id = None;
+
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseInputError::new("\"]\"").at_str(input, line))?;
+                let (day, name) = match name.split_once('.') {
+                    Some((day, name)) => (day, Some(name)),
+                    None => (name, None),
+                };
+                let number = parse::prefixed(input, day, "day")?;
+                let day = parse::token(input, number, "a day number")?;
+                if let Some(name) = name.filter(|name| !inputs::is_valid_name(name)) {
+                    return Err(ParseInputError::new("an input name").at_str(input, name));
+                }
+                let table = InputId::new(day, name);
+                inputs.insert(table.clone());
+                id = Some(table);
+                continue;
+            }
+
+            let id = id
+                .clone()
+                .ok_or_else(|| ParseInputError::new("a [dayN] table").at_str(input, line))?;
+            let (key, value) = parse::split_once(input, line, "=")?;
+            let number = parse::prefixed(input, key.trim(), "part")?;
+            let part = parse::token(input, number, "a part number")?;
+            let value = value.trim();
+            let answer = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .strip_suffix('"')
+                    .ok_or_else(|| ParseInputError::new("a closing '\"'").at_str(input, value))?,
+                None => {
+                    parse::token::<i64>(input, value, "a quoted string or an integer")?;
+                    value
+                }
+            };
+            answers.insert((id, part), answer.to_string());
+        }
+
+        Ok(Self { answers, inputs })
+    }
+
+    /// Loads the answers at `path`. A missing file is treated as empty.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(input) => Self::parse(&input).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Couldn't read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// The answer for the default input of `day`.
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.get_input(&InputId::new(day, None), part)
+    }
+
+    pub fn get_input(&self, id: &InputId, part: u32) -> Option<&str> {
+        self.answers.get(&(id.clone(), part)).map(String::as_str)
+    }
+
+    /// The inputs with a table, in order, even if it's empty.
+    pub fn inputs(&self) -> impl Iterator<Item = &InputId> {
+        self.inputs.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{InputStore, INPUT_DIR};
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# comment\n\n[day1]\npart1 = \"11\"\npart2 = 31\n\n[day18]\npart2 = \"6,1\"\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(18, 2), Some("6,1"));
+        assert_eq!(answers.get(18, 1), None);
+
+        let answers = Answers::parse("[day12.sample2]\npart2 = 236\n[day14.sample1]\n").unwrap();
+        assert_eq!(
+            answers.get_input(&InputId::new(12, Some("sample2")), 2),
+            Some("236")
+        );
+        assert_eq!(answers.get(12, 2), None);
+        assert_eq!(
+            answers.inputs().collect::<Vec<_>>(),
+            [
+                &InputId::new(12, Some("sample2")),
+                &InputId::new(14, Some("sample1"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Answers::parse("part1 = \"11\"").unwrap_err();
+        assert_eq!(err.expected, "a [dayN] table");
+
+        let err = Answers::parse("[day1]\npart1 = \"11").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(9)));
+
+        let err = Answers::parse("[day1]\npart1 = eleven").unwrap_err();
+        assert_eq!(err.expected, "a quoted string or an integer");
+
+        let err = Answers::parse("[dayone]").unwrap_err();
+        assert_eq!(err.column, Some(5));
+
+        let err = Answers::parse("[day1.a b]").unwrap_err();
+        assert_eq!(
+            (err.expected.as_str(), err.column),
+            ("an input name", Some(7))
+        );
+    }
+
+    /// Runs every part of `day` on its real input, if that input is present,
+    /// and compares against `answers/2024.toml`. The input must also match
+    /// its recorded checksum.
+    fn check_real_input(day: u32) {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let store = InputStore::open(root.join(INPUT_DIR)).unwrap();
+        let id = InputId::new(day, None);
+        if !store.exists(&id) {
+            eprintln!("Skipping day {}: no input", day);
+            return;
+        }
+        let input = store.read(&id).unwrap();
+        let answers = Answers::load(root.join(ANSWERS_PATH)).unwrap();
+        let solution = crate::solutions::find(day).unwrap();
+
+        for part in 1..=2 {
+            let expected = answers.get(day, part).expect("every day has known answers");
+            let answer = solution.solve(&input, part).unwrap();
+            assert_eq!(answer, expected, "day {} part {}", day, part);
+        }
+    }
+
+    macro_rules! real_input_tests {
+        ($($name:ident: $day:literal,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    check_real_input($day);
+                }
+            )*
+        };
+    }
+
+    real_input_tests! {
+        test_real_input_day1: 1,
+        test_real_input_day2: 2,
+        test_real_input_day3: 3,
+        test_real_input_day4: 4,
+        test_real_input_day5: 5,
+        test_real_input_day6: 6,
+        test_real_input_day7: 7,
+        test_real_input_day8: 8,
+        test_real_input_day9: 9,
+        test_real_input_day10: 10,
+        test_real_input_day11: 11,
+        test_real_input_day12: 12,
+        test_real_input_day13: 13,
+        test_real_input_day14: 14,
+        test_real_input_day15: 15,
+        test_real_input_day16: 16,
+        test_real_input_day17: 17,
+        test_real_input_day18: 18,
+        test_real_input_day19: 19,
+        test_real_input_day20: 20,
+    }
+}
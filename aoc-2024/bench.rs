@@ -0,0 +1,258 @@
+//! A small std-only benchmark harness: warm up, time as many runs as fit in a
+//! budget, and summarise them.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    json::{Object, ToJson},
+    table::{self, Align},
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// How long to run the code untimed before measuring.
+    pub warmup: Duration,
+    /// How long to keep taking samples for, once `min_runs` are taken.
+    pub budget: Duration,
+    pub min_runs: usize,
+    pub max_runs: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_runs: 5,
+            max_runs: 1000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        let nanos = sorted.iter().map(Duration::as_nanos).collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<u128>() / runs as u128;
+        let variance = nanos
+            .iter()
+            .map(|&n| (n as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        })
+    }
+}
+
+/// Times `f` according to `config`. Stops at the first error, returning it.
+pub fn measure<T, E>(config: &Config, mut f: impl FnMut() -> Result<T, E>) -> Result<Stats, E> {
+    let warmup = Instant::now();
+    while warmup.elapsed() < config.warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < config.max_runs
+        && (samples.len() < config.min_runs || start.elapsed() < config.budget)
+    {
+        let run = Instant::now();
+        black_box(f()?);
+        samples.push(run.elapsed());
+    }
+
+    Ok(Stats::from_samples(&samples).unwrap_or_default())
+}
+
+/// The timings for one day and part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+/// Renders `records` as an aligned text table, with a row each for parsing
+/// and solving.
+pub fn table(records: &[Record]) -> String {
+    let header = [
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        ("Phase", Align::Left),
+        ("Min", Align::Right),
+        ("Median", Align::Right),
+        ("Mean", Align::Right),
+        ("Stddev", Align::Right),
+        ("Runs", Align::Right),
+    ];
+    let rows = records
+        .iter()
+        .flat_map(|record| {
+            [("parse", &record.parse), ("solve", &record.solve)].map(|(phase, stats)| {
+                [
+                    record.day.to_string(),
+                    record.part.to_string(),
+                    phase.to_string(),
+                    format_duration(stats.min),
+                    format_duration(stats.median),
+                    format_duration(stats.mean),
+                    format_duration(stats.stddev),
+                    stats.runs.to_string(),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    table::render(header, &rows)
+}
+
+fn stats_json(stats: &Stats) -> Object {
+    Object::new()
+        .field("runs", stats.runs)
+        .field("min_ns", stats.min.as_nanos())
+        .field("median_ns", stats.median.as_nanos())
+        .field("mean_ns", stats.mean.as_nanos())
+        .field("stddev_ns", stats.stddev.as_nanos())
+}
+
+/// Renders `records` as JSON, one record per line so saved runs diff well.
+pub fn to_json(records: &[Record]) -> String {
+    let lines = records
+        .iter()
+        .map(|record| {
+            let object = Object::new()
+                .field("day", record.day)
+                .field("part", record.part)
+                .field("parse", stats_json(&record.parse))
+                .field("solve", stats_json(&record.solve));
+            format!("    {}", object.to_json())
+        })
+        .collect::<Vec<_>>();
+    format!("{{\n  \"results\": [\n{}\n  ]\n}}\n", lines.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&ms(&[4, 2, 8, 6])).unwrap();
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 2236);
+
+        let stats = Stats::from_samples(&ms(&[3, 1, 2])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(2));
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_measure() {
+        let config = Config {
+            warmup: Duration::ZERO,
+            budget: Duration::ZERO,
+            min_runs: 3,
+            max_runs: 10,
+        };
+        let mut calls = 0;
+        let stats = measure(&config, || {
+            calls += 1;
+            Ok::<_, ()>(calls)
+        })
+        .unwrap();
+        assert_eq!(stats.runs, 3);
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result = measure(&config, || {
+            calls += 1;
+            if calls == 2 {
+                Err("failed")
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err("failed"));
+    }
+
+    #[test]
+    fn test_output() {
+        let stats = Stats::from_samples(&ms(&[1, 3])).unwrap();
+        let records = [Record {
+            day: 6,
+            part: 2,
+            parse: Stats::default(),
+            solve: stats,
+        }];
+
+        let table = table(&records);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        let columns = |line: &str| {
+            line.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            columns(lines[0]),
+            ["Day", "Part", "Phase", "Min", "Median", "Mean", "Stddev", "Runs"]
+        );
+        assert_eq!(
+            columns(lines[1]),
+            ["6", "2", "parse", "0.00ns", "0.00ns", "0.00ns", "0.00ns", "0"]
+        );
+        assert_eq!(
+            columns(lines[2]),
+            ["6", "2", "solve", "1.00ms", "2.00ms", "2.00ms", "1.00ms", "2"]
+        );
+
+        assert_eq!(
+            to_json(&records),
+            "{\n  \"results\": [\n    {\"day\": 6, \"part\": 2, \
+             \"parse\": {\"runs\": 0, \"min_ns\": 0, \"median_ns\": 0, \"mean_ns\": 0, \"stddev_ns\": 0}, \
+             \"solve\": {\"runs\": 2, \"min_ns\": 1000000, \"median_ns\": 2000000, \"mean_ns\": 2000000, \"stddev_ns\": 1000000}}\n  ]\n}\n"
+        );
+    }
+}
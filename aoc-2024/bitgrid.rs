@@ -0,0 +1,274 @@
+use crate::direction::Direction;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size grid of booleans packed into 64-bit words. Out-of-bounds
+/// positions read as unset and ignore writes, like [`crate::vec2d::Vec2D`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    size: (usize, usize),
+}
+
+impl BitGrid {
+    pub fn new(size: (usize, usize)) -> Self {
+        Self {
+            words: vec![0; (size.0 * size.1).div_ceil(WORD_BITS)],
+            size,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<(usize, u64)> {
+        if x >= self.size.0 || y >= self.size.1 {
+            return None;
+        }
+        let bit = x * self.size.1 + y;
+        Some((bit / WORD_BITS, 1 << (bit % WORD_BITS)))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        match self.index(x, y) {
+            Some((word, mask)) => self.words[word] & mask != 0,
+            None => false,
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        if let Some((word, mask)) = self.index(x, y) {
+            self.words[word] |= mask;
+        }
+    }
+
+    pub fn unset(&mut self, x: usize, y: usize) {
+        if let Some((word, mask)) = self.index(x, y) {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Sets the bit and returns whether it was previously unset, like
+    /// `HashSet::insert`.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        match self.index(x, y) {
+            Some((word, mask)) => {
+                let was_unset = self.words[word] & mask == 0;
+                self.words[word] |= mask;
+                was_unset
+            }
+            None => false,
+        }
+    }
+
+    /// Unsets every bit, keeping the allocation for the next run.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        assert_eq!(self.size, other.size, "BitGrid sizes differ");
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= other;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        assert_eq!(self.size, other.size, "BitGrid sizes differ");
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word &= other;
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// Yields the position of every set bit, row by row.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let width = self.size.1;
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(idx, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(idx * WORD_BITS + bit)
+                })
+            })
+            .map(move |bit| (bit / width, bit % width))
+    }
+}
+
+/// A [`BitGrid`] with one bit per cardinal direction in every cell, for
+/// tracking visited `(position, direction)` states.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid4 {
+    bits: BitGrid,
+}
+
+impl BitGrid4 {
+    pub fn new(size: (usize, usize)) -> Self {
+        Self {
+            bits: BitGrid::new((size.0, size.1 * 4)),
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.bits.size.0, self.bits.size.1 / 4)
+    }
+
+    fn column(&self, y: usize, direction: Direction) -> usize {
+        let offset = match direction {
+            Direction::Up => 0,
+            Direction::Right => 1,
+            Direction::Down => 2,
+            Direction::Left => 3,
+            _ => panic!("BitGrid4 only stores cardinal directions"),
+        };
+        if y >= self.size().1 {
+            // Keep out-of-bounds columns out of bounds after scaling.
+            return usize::MAX;
+        }
+        y * 4 + offset
+    }
+
+    pub fn get(&self, x: usize, y: usize, direction: Direction) -> bool {
+        self.bits.get(x, self.column(y, direction))
+    }
+
+    /// Returns whether any direction is set in the cell.
+    pub fn any(&self, x: usize, y: usize) -> bool {
+        [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ]
+        .into_iter()
+        .any(|direction| self.get(x, y, direction))
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, direction: Direction) {
+        let column = self.column(y, direction);
+        self.bits.set(x, column);
+    }
+
+    pub fn unset(&mut self, x: usize, y: usize, direction: Direction) {
+        let column = self.column(y, direction);
+        self.bits.unset(x, column);
+    }
+
+    /// Sets the bit and returns whether it was previously unset.
+    pub fn insert(&mut self, x: usize, y: usize, direction: Direction) -> bool {
+        let column = self.column(y, direction);
+        self.bits.insert(x, column)
+    }
+
+    /// Unsets every bit, keeping the allocation for the next run.
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid4) {
+        self.bits.union_with(&other.bits);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid4) {
+        self.bits.intersect_with(&other.bits);
+    }
+
+    /// Collapses the directions into one bit per cell.
+    pub fn positions(&self) -> BitGrid {
+        let mut result = BitGrid::new(self.size());
+        for (x, column) in self.bits.iter_ones() {
+            result.set(x, column / 4);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitgrid() {
+        let mut grid = BitGrid::new((9, 9));
+        assert!(grid.insert(0, 0));
+        assert!(!grid.insert(0, 0));
+        grid.set(8, 8);
+        grid.set(9, 0);
+        grid.set(7, 1);
+        grid.unset(7, 1);
+
+        assert!(grid.get(8, 8));
+        assert!(!grid.get(7, 1));
+        assert!(!grid.get(9, 0));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.iter_ones().collect::<Vec<_>>(), vec![(0, 0), (8, 8)]);
+
+        grid.clear();
+        assert_eq!(grid.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut a = BitGrid::new((3, 3));
+        let mut b = BitGrid::new((3, 3));
+        a.set(0, 0);
+        a.set(1, 1);
+        b.set(1, 1);
+        b.set(2, 2);
+
+        assert_eq!(a.union(&b).count_ones(), 3);
+        assert_eq!(
+            a.intersection(&b).iter_ones().collect::<Vec<_>>(),
+            vec![(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_bitgrid4() {
+        let mut grid = BitGrid4::new((2, 3));
+        assert!(grid.insert(1, 2, Direction::Left));
+        assert!(!grid.insert(1, 2, Direction::Left));
+        assert!(grid.insert(1, 2, Direction::Up));
+        grid.set(0, 3, Direction::Up);
+
+        assert!(grid.get(1, 2, Direction::Up));
+        assert!(!grid.get(1, 2, Direction::Down));
+        assert!(grid.any(1, 2));
+        assert!(!grid.any(0, 2));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(
+            grid.positions().iter_ones().collect::<Vec<_>>(),
+            vec![(1, 2)]
+        );
+    }
+}
@@ -1,88 +1,76 @@
-use std::collections::hash_map;
+use std::{collections::hash_map, error::Error};
 
-#[aoc(day1, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut total = 0;
+use crate::{errors::ParseInputError, parse, solutions::Solution};
 
-    let mut list1: Vec<i32> = vec![];
-    let mut list2: Vec<i32> = vec![];
+pub struct Day1;
 
-    // Remove first line
-    input.lines().for_each(|l| match l.split_once("   ") {
-        Some((word1, word2)) => {
-            list1.push(word1.parse().unwrap());
-            list2.push(word2.parse().unwrap());
-        }
-        None => {
-            panic!("Invalid input: {}", l);
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Parsed<'a> = (Vec<i32>, Vec<i32>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let mut list1: Vec<i32> = vec![];
+        let mut list2: Vec<i32> = vec![];
+
+        for line in input.lines() {
+            let (a, b) = parse::pair(input, line, "   ", "a location ID")?;
+            list1.push(a);
+            list2.push(b);
         }
-    });
 
-    list1.sort();
-    list2.sort();
+        Ok((list1, list2))
+    }
+
+    fn part1((list1, list2): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut list1 = list1.clone();
+        let mut list2 = list2.clone();
 
-    for i in 0..list1.len() {
-        total += (list1[i] - list2[i]).abs();
+        list1.sort();
+        list2.sort();
+
+        Ok(list1.iter().zip(&list2).map(|(a, b)| (a - b).abs()).sum())
     }
 
-    return total;
-}
+    fn part2((list1, list2): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total = 0;
 
-#[aoc(day1, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut total = 0;
+        let mut map: hash_map::HashMap<i32, i32> = hash_map::HashMap::new();
+        list2.iter().for_each(|&x| {
+            *map.entry(x).or_insert(0) += 1;
+        });
+        list1.iter().for_each(|&x| {
+            if let Some(count) = map.get(&x) {
+                total += count * x;
+            }
+        });
 
-    let mut list1: Vec<i32> = vec![];
-    let mut list2: Vec<i32> = vec![];
+        Ok(total)
+    }
+}
 
-    // Remove first line
-    input.lines().for_each(|l| match l.split_once("   ") {
-        Some((word1, word2)) => {
-            list1.push(word1.parse().unwrap());
-            list2.push(word2.parse().unwrap());
-        }
-        None => {
-            panic!("Invalid input: {}", l);
-        }
-    });
-
-    let mut map: hash_map::HashMap<i32, i32> = hash_map::HashMap::new();
-    list2.iter().for_each(|&x| {
-        if map.contains_key(&x) {
-            map.insert(x, map.get(&x).unwrap() + 1);
-        } else {
-            map.insert(x, 1);
-        }
-    });
-    list1.iter().for_each(|&x| {
-        if map.contains_key(&x) {
-            total += map.get(&x).unwrap() * x;
-        }
-    });
+#[aoc(day1, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day1::solve_part1(input)
+}
 
-    total
+#[aoc(day1, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day1::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-3   4
-4   3
-2   5
-1   3
-3   9
-3   3
-";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 11);
-    }
+    fn test_invalid_input() {
+        let err = Day1::parse_input("3   4\n4 3\n").err().unwrap();
+        assert_eq!(err.day, Some(1));
+        assert_eq!(err.line, Some(2));
 
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 31);
+        let err = Day1::parse_input("3   x\n").err().unwrap();
+        assert_eq!((err.line, err.column), (Some(1), Some(5)));
     }
 }
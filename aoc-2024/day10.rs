@@ -1,29 +1,18 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, error::Error};
 
-use crate::{direction::Direction, vec2d::Vec2D};
-
-const DIRS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Down,
-    Direction::Left,
-    Direction::Right,
-];
+use crate::{errors::ParseInputError, solutions::Solution, vec2d::Vec2D};
 
 pub fn dfs(map: &Vec2D<char>, start: (usize, usize)) -> i32 {
     let mut stack = vec![(0, start)];
     let mut visited = HashSet::new();
 
     while let Some((value, (x, y))) = stack.pop() {
-        for dir in DIRS {
-            if let Some((nx, ny)) = dir.checked_add((x, y)) {
-                if let Some(c) = map.get(nx, ny) {
-                    if *c.to_string() == (value + 1).to_string() {
-                        if *c == '9' {
-                            visited.insert((nx, ny));
-                        }
-                        stack.push((value + 1, (nx, ny)));
-                    }
+        for ((nx, ny), c) in map.neighbors4((x, y)) {
+            if *c.to_string() == (value + 1).to_string() {
+                if *c == '9' {
+                    visited.insert((nx, ny));
                 }
+                stack.push((value + 1, (nx, ny)));
             }
         }
     }
@@ -31,91 +20,90 @@ pub fn dfs(map: &Vec2D<char>, start: (usize, usize)) -> i32 {
     visited.len() as i32
 }
 
-#[aoc(day10, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut total: i32 = 0;
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Parsed<'a> = Vec2D<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Ok(Vec2D::parse(input, |c| {
+            (c.is_ascii_digit() || c == '.').then_some(c)
+        })?)
+    }
 
-    let map = Vec2D::new_chars(input);
+    fn part1(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total: i32 = 0;
 
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '0' {
-                    total += dfs(&map, (i, j));
+        for i in 0..map.size.0 {
+            for j in 0..map.size.1 {
+                if let Some(c) = map.get(i, j) {
+                    if *c == '0' {
+                        total += dfs(map, (i, j));
+                    }
                 }
             }
         }
+
+        Ok(total)
     }
 
-    total
-}
+    fn part2(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total: i32 = 0;
 
-#[aoc(day10, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut total: i32 = 0;
-
-    let mut queue = vec![HashSet::<(usize, usize)>::new(); 10];
-    let map = Vec2D::new_chars(input);
-    let mut rank = Vec2D::new_default(map.size, 0);
-
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '0' {
-                    queue[0].insert((i, j));
-                    rank.set(i, j, 1);
+        let mut queue = vec![HashSet::<(usize, usize)>::new(); 10];
+        let mut rank = Vec2D::new_default(map.size, 0);
+
+        for i in 0..map.size.0 {
+            for j in 0..map.size.1 {
+                if let Some(c) = map.get(i, j) {
+                    if *c == '0' {
+                        queue[0].insert((i, j));
+                        rank.set(i, j, 1);
+                    }
                 }
             }
         }
-    }
 
-    for i in 0..9 {
-        for (x, y) in queue[i].clone() {
-            for dir in DIRS {
-                if let Some((nx, ny)) = dir.checked_add((x, y)) {
-                    if let Some(c) = map.get(nx, ny) {
-                        if *c.to_string() == (i + 1).to_string() {
-                            queue.get_mut(i + 1).unwrap().insert((nx, ny));
-                            if i == 8 {
-                                total += rank.get(x, y).unwrap();
-                            } else {
-                                rank.set(
-                                    nx,
-                                    ny,
-                                    rank.get(nx, ny).unwrap() + rank.get(x, y).unwrap(),
-                                );
-                            }
+        for i in 0..9 {
+            for (x, y) in queue[i].clone() {
+                for ((nx, ny), c) in map.neighbors4((x, y)) {
+                    if *c.to_string() == (i + 1).to_string() {
+                        queue.get_mut(i + 1).unwrap().insert((nx, ny));
+                        if i == 8 {
+                            total += rank.get(x, y).unwrap();
+                        } else {
+                            rank.set(nx, ny, rank.get(nx, ny).unwrap() + rank.get(x, y).unwrap());
                         }
                     }
                 }
             }
         }
+
+        Ok(total)
     }
+}
+
+#[aoc(day10, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day10::solve_part1(input)
+}
 
-    total
+#[aoc(day10, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day10::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 36);
-    }
-
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 81);
+    fn test_invalid_input() {
+        let err = Day10::parse_input("0123\n12a4\n").unwrap_err();
+        assert_eq!(err.day, Some(10));
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
     }
 }
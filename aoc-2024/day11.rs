@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error};
+
+use crate::{errors::ParseInputError, parse, solutions::Solution};
 
 fn dp(cache: &mut HashMap<(u64, usize), u64>, value: u64, blink: usize) -> u64 {
     if blink == 0 {
@@ -8,70 +10,62 @@ fn dp(cache: &mut HashMap<(u64, usize), u64>, value: u64, blink: usize) -> u64 {
         return *count;
     }
 
-    if value == 0 {
-        let count = dp(cache, 1, blink - 1);
-        cache.insert((value, blink), count);
-        return count;
-    } else if value.to_string().len() % 2 == 0 {
-        let str = value.to_string();
-        let (a, b) = str.split_at(str.len() / 2);
-        let count = dp(cache, a.parse::<u64>().unwrap(), blink - 1)
-            + dp(cache, b.parse::<u64>().unwrap(), blink - 1);
-        cache.insert((value, blink), count);
-        return count;
+    let digits = value.to_string().len() as u32;
+    let count = if value == 0 {
+        dp(cache, 1, blink - 1)
+    } else if digits.is_multiple_of(2) {
+        let half = 10u64.pow(digits / 2);
+        dp(cache, value / half, blink - 1) + dp(cache, value % half, blink - 1)
     } else {
-        let count = dp(cache, value * 2024, blink - 1);
-        cache.insert((value, blink), count);
-        return count;
-    }
+        dp(cache, value * 2024, blink - 1)
+    };
+    cache.insert((value, blink), count);
+    count
 }
 
-#[aoc(day11, part1)]
-pub fn part1(input: &str) -> u64 {
-    let mut total = 0;
-    let mut cache = HashMap::new();
-    let stones = input
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
+pub struct Day11;
 
-    for j in 0..stones.len() {
-        total += dp(&mut cache, stones[j], 25);
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Parsed<'a> = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        parse::list(input, input, " ", "a stone number")
     }
 
-    total
-}
+    fn part1(stones: &Self::Parsed<'_>) -> Result<u64, Box<dyn Error>> {
+        let mut cache = HashMap::new();
 
-#[aoc(day11, part2)]
-pub fn part2(input: &str) -> u64 {
-    let mut total = 0;
-    let mut cache = HashMap::new();
-    let stones = input
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
+        Ok(stones.iter().map(|&stone| dp(&mut cache, stone, 25)).sum())
+    }
 
-    for j in 0..stones.len() {
-        total += dp(&mut cache, stones[j], 75);
+    fn part2(stones: &Self::Parsed<'_>) -> Result<u64, Box<dyn Error>> {
+        let mut cache = HashMap::new();
+
+        Ok(stones.iter().map(|&stone| dp(&mut cache, stone, 75)).sum())
     }
+}
 
-    total
+#[aoc(day11, part1)]
+pub fn part1(input: &str) -> Result<u64, Box<dyn Error>> {
+    Day11::solve_part1(input)
+}
+
+#[aoc(day11, part2)]
+pub fn part2(input: &str) -> Result<u64, Box<dyn Error>> {
+    Day11::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-125 17";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 55312);
-    }
-
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 65601038650482);
+    fn test_invalid_input() {
+        let err = Day11::parse_input("125 -17").unwrap_err();
+        assert_eq!(err.day, Some(11));
+        assert_eq!(err.found.as_deref(), Some("\"-17\""));
     }
 }
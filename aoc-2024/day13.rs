@@ -1,115 +1,224 @@
-use std::cmp::min;
+use std::error::Error;
 
 use itertools::Itertools;
-use regex::Regex;
-
-fn parse_line(line: &str) -> (i64, i64) {
-    let regexp = Regex::new(r".+: X[+=](\d+), Y[+=](\d+)").unwrap();
-    let [x, y] = regexp
-        .captures(line)
-        .unwrap()
-        .extract()
-        .1
-        .map(|x| x.parse::<i64>().unwrap());
-    (x, y)
+
+use crate::{errors::ParseInputError, parse, point::Point, solutions::Solution};
+
+/// Button A's move, button B's move and the prize.
+type Machine = (Point<i64>, Point<i64>, Point<i64>);
+
+fn parse_line(input: &str, line: &str) -> Result<Point<i64>, ParseInputError> {
+    let [x, y] = parse::ints_n(input, line)?;
+    Ok(Point::new(x, y))
 }
 
-#[aoc(day13, part1)]
-pub fn part1(input: &str) -> i64 {
-    let mut total = 0;
-
-    for input_group in input.split("\n\n") {
-        let ((ax, ay), (bx, by), (px, py)) = input_group
-            .lines()
-            .map(|line| parse_line(line))
-            .collect_tuple()
-            .unwrap();
-
-        if ax * by - bx * ay == 0 || bx * ay - by * ax == 0 {
-            total += min((px / ax) * 3, px / bx);
-            break;
+/// The fewest tokens that win the prize pressing each button at most
+/// `max_presses` times, if it can be won at all. Button A costs 3 tokens and
+/// button B costs 1.
+fn cheapest((a, b, prize): Machine, max_presses: i64) -> Option<i64> {
+    let (Point { x: ax, y: ay }, Point { x: bx, y: by }) = (a, b);
+    let Point { x: px, y: py } = prize;
+    let det = ax * by - bx * ay;
+    if det != 0 {
+        let a = px * by - py * bx;
+        let b = ax * py - ay * px;
+        if a % det != 0 || b % det != 0 {
+            return None;
         }
+        let (a, b) = (a / det, b / det);
+        let presses = 0..=max_presses;
+        return (presses.contains(&a) && presses.contains(&b)).then_some(3 * a + b);
+    }
 
-        if (px * by - py * bx) % (ax * by - bx * ay) != 0 {
-            continue;
-        }
-        let x = (px * by - py * bx) / (ax * by - bx * ay);
-        if (px * ay - py * ax) % (bx * ay - by * ax) != 0 {
-            continue;
-        }
-        let y = (px * ay - py * ax) / (bx * ay - by * ax);
+    // Both buttons move along the same line, so the prize has to be on it
+    // too, and then one coordinate decides how far the presses go.
+    let on_line = if (ax, ay) != (0, 0) {
+        ax * py - ay * px == 0
+    } else {
+        bx * py - by * px == 0
+    };
+    if !on_line {
+        return None;
+    }
+    if (ax, bx) != (0, 0) {
+        cheapest_on_line(ax, bx, px, max_presses)
+    } else if (ay, by) != (0, 0) {
+        cheapest_on_line(ay, by, py, max_presses)
+    } else {
+        ((px, py) == (0, 0)).then_some(0)
+    }
+}
 
-        total += x * 3 + y;
+/// The fewest tokens for `a` presses of A and `b` presses of B, each from 0
+/// to `max_presses`, with `a * da + b * db == target`. `da` and `db` can't
+/// both be 0.
+fn cheapest_on_line(da: i64, db: i64, target: i64, max_presses: i64) -> Option<i64> {
+    let max = max_presses as i128;
+    let (da, db, target) = (da as i128, db as i128, target as i128);
+    let presses = |n: i128, d: i128| Some(n / d).filter(|p| n % d == 0 && (0..=max).contains(p));
+    if da == 0 {
+        return presses(target, db).and_then(|b| i64::try_from(b).ok());
+    }
+    if db == 0 {
+        return presses(target, da).and_then(|a| i64::try_from(3 * a).ok());
     }
 
-    total
+    let (g, s, t) = extended_gcd(da, db);
+    if target % g != 0 {
+        return None;
+    }
+    // Every solution is a = a0 + k * step_a, b = b0 + k * step_b.
+    let (a0, b0) = (s * (target / g), t * (target / g));
+    let (step_a, step_b) = (db / g, -da / g);
+    let (lo_a, hi_a) = steps_within(a0, step_a, max);
+    let (lo_b, hi_b) = steps_within(b0, step_b, max);
+    let (lo, hi) = (lo_a.max(lo_b), hi_a.min(hi_b));
+    if lo > hi {
+        return None;
+    }
+
+    // The cost is linear in k, so one of the ends is the cheapest.
+    let cost = |k: i128| 3 * (a0 + k * step_a) + b0 + k * step_b;
+    i64::try_from(cost(lo).min(cost(hi))).ok()
 }
 
-#[aoc(day13, part2)]
-pub fn part2(input: &str) -> i64 {
-    let mut total = 0;
-
-    for input_group in input.split("\n\n") {
-        let ((ax, ay), (bx, by), (prize_x, prize_y)) = input_group
-            .lines()
-            .map(|line| parse_line(line))
-            .collect_tuple()
-            .unwrap();
-        let px = prize_x + 10000000000000;
-        let py = prize_y + 10000000000000;
-
-        if ax * by - bx * ay == 0 || bx * ay - by * ax == 0 {
-            total += min((px / ax) * 3, px / bx);
-            break;
-        }
+/// Returns `(g, s, t)` with `g` the gcd of `a` and `b`, and `a * s + b * t == g`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a.abs(), a.signum(), 0);
+    }
+    let (g, s, t) = extended_gcd(b, a % b);
+    (g, t, s - (a / b) * t)
+}
+
+/// The range of `k` for which `start + k * step` is from 0 to `max`. `step`
+/// isn't 0.
+fn steps_within(start: i128, step: i128, max: i128) -> (i128, i128) {
+    let ceil = |n: i128, d: i128| -(-n).div_euclid(d);
+    if step > 0 {
+        (ceil(-start, step), (max - start).div_euclid(step))
+    } else {
+        (ceil(start - max, -step), start.div_euclid(-step))
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Parsed<'a> = Vec<Machine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        parse::sections(input)
+            .into_iter()
+            .map(|input_group| {
+                input_group
+                    .lines()
+                    .map(|line| parse_line(input, line))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .collect_tuple()
+                    .ok_or_else(|| {
+                        ParseInputError::new("two buttons and a prize").at_str(input, input_group)
+                    })
+            })
+            .collect()
+    }
 
-        if (px * by - py * bx) % (ax * by - bx * ay) != 0 {
-            continue;
+    fn part1(machines: &Self::Parsed<'_>) -> Result<i64, Box<dyn Error>> {
+        let mut total = 0;
+
+        for &machine in machines {
+            total += cheapest(machine, 100).unwrap_or(0);
         }
-        let x = (px * by - py * bx) / (ax * by - bx * ay);
-        if (px * ay - py * ax) % (bx * ay - by * ax) != 0 {
-            continue;
+
+        Ok(total)
+    }
+
+    fn part2(machines: &Self::Parsed<'_>) -> Result<i64, Box<dyn Error>> {
+        let mut total = 0;
+
+        let offset = Point::new(10000000000000, 10000000000000);
+        for &(a, b, prize) in machines {
+            total += cheapest((a, b, prize + offset), i64::MAX).unwrap_or(0);
         }
-        let y = (px * ay - py * ax) / (bx * ay - by * ax);
 
-        total += x * 3 + y;
+        Ok(total)
     }
+}
+
+#[aoc(day13, part1)]
+pub fn part1(input: &str) -> Result<i64, Box<dyn Error>> {
+    Day13::solve_part1(input)
+}
 
-    total
+#[aoc(day13, part2)]
+pub fn part2(input: &str) -> Result<i64, Box<dyn Error>> {
+    Day13::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
+    use super::*;
 
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
+    #[test]
+    fn test_invalid_input() {
+        let err = Day13::parse_input("Button A: X+94, Y+34\nButton B: X+22\nPrize: X=8400, Y=5400")
+            .unwrap_err();
+        assert_eq!(err.day, Some(13));
+        assert_eq!(err.line, Some(2));
+
+        let err = Day13::parse_input("Button A: X+94, Y+34\nButton B: X+22, Y+67").unwrap_err();
+        assert_eq!(err.expected, "two buttons and a prize");
+    }
 
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279
+    type Tuples = ((i64, i64), (i64, i64), (i64, i64));
 
-Button A: X+1, Y+1
-Button B: X+2, Y+2
-Prize: X=10, Y=10";
+    fn machine((a, b, prize): Tuples) -> Machine {
+        (a.into(), b.into(), prize.into())
+    }
 
-    use super::*;
+    fn cheapest_any(tuples: Tuples) -> Option<i64> {
+        cheapest(machine(tuples), i64::MAX)
+    }
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 485);
+    fn test_parallel_buttons() {
+        // Neither button moves along X, so the prize can't be reached, and
+        // the machine after it still counts.
+        let input = "Button A: X+0, Y+5\nButton B: X+0, Y+3\nPrize: X=10, Y=10\n\n\
+                     Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n";
+        assert_eq!(part1(input).unwrap(), 280);
+
+        // Pressing B five times beats any mix with A.
+        assert_eq!(cheapest_any(((2, 2), (1, 1), (5, 5))), Some(5));
+        // Two presses of A beat twelve of B.
+        assert_eq!(cheapest_any(((6, 6), (1, 1), (12, 12))), Some(6));
+        // Only one press of A and two of B reach 9.
+        assert_eq!(cheapest_any(((5, 5), (2, 2), (9, 9))), Some(5));
+        // B moves backwards, so there are solutions with any number of A
+        // presses past the fewest.
+        assert_eq!(cheapest_any(((3, 0), (-1, 0), (5, 0))), Some(7));
+        assert_eq!(cheapest_any(((4, 4), (6, 6), (7, 7))), None);
+        assert_eq!(cheapest_any(((2, 2), (1, 1), (5, 6))), None);
+        assert_eq!(cheapest_any(((0, 0), (0, 0), (0, 0))), Some(0));
+        assert_eq!(cheapest(machine(((5, 5), (2, 2), (9, 9))), 1), None);
     }
 
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 5875318608913);
+    fn test_press_limits() {
+        // Reaching the prize takes -1 presses of A and 4 of B.
+        assert_eq!(cheapest_any(((10, 20), (20, 10), (70, 20))), None);
+
+        // 101 presses of A and 1 of B.
+        let tuples = ((1, 2), (3, 1), (104, 203));
+        assert_eq!(cheapest(machine(tuples), 100), None);
+        assert_eq!(cheapest_any(tuples), Some(304));
+
+        let input = "Button A: X+10, Y+20\nButton B: X+20, Y+10\nPrize: X=70, Y=20\n\n\
+                     Button A: X+1, Y+2\nButton B: X+3, Y+1\nPrize: X=104, Y=203\n";
+        assert_eq!(part1(input).unwrap(), 0);
     }
 }
@@ -1,114 +1,83 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, error::Error};
 
-use itertools::Itertools;
+use crate::{
+    errors::ParseInputError, parse, point::Point, solutions::Solution, torus::TorusGrid,
+    vec2d::Vec2D,
+};
 
-use crate::{direction::Direction, vec2d::Vec2D};
+const SPACE_SIZE: (usize, usize) = (103, 101);
 
-const MAX_TILES_X: i32 = 101;
-const MAX_TILES_Y: i32 = 103;
-
-#[derive(Debug)]
-struct Robot {
-    pub position: (i32, i32),
-    velocity: (i32, i32),
-}
-
-fn parse_coordinate(part: &str) -> (i32, i32) {
-    let coordinate_str = part.replace("p=", "").replace("v=", "");
-    let mut pos = coordinate_str.split(',').map(|x| x.parse::<i32>().unwrap());
-    (pos.next().unwrap(), pos.next().unwrap())
+/// Position and velocity are stored as (row, column), i.e. `(y, x)` in the
+/// puzzle's notation.
+#[derive(Clone, Debug)]
+pub struct Robot {
+    pub position: Point<usize>,
+    velocity: Point<i64>,
 }
 
 impl Robot {
-    pub fn new(position: (i32, i32), velocity: (i32, i32)) -> Self {
+    pub fn new(position: Point<usize>, velocity: Point<i64>) -> Self {
         Self { position, velocity }
     }
 
-    pub fn from_input(input: &str) -> Self {
-        let (position_str, velocity_str) = input.split_once(" ").unwrap();
+    /// Parses the robot described by `line`, a line of `input`.
+    pub fn from_input<T>(
+        input: &str,
+        line: &str,
+        space: &TorusGrid<T>,
+    ) -> Result<Self, ParseInputError> {
+        let [px, py, vx, vy] = parse::ints_n(input, line)?;
 
-        Robot::new(
-            parse_coordinate(position_str),
-            parse_coordinate(velocity_str),
-        )
+        Ok(Robot::new(space.wrap((py, px)).into(), Point::new(vy, vx)))
     }
 
-    fn next(&mut self) {
-        self.position.0 += self.velocity.0;
-        self.position.1 += self.velocity.1;
+    /// Where the robot is after `seconds`.
+    fn after<T>(&self, space: &TorusGrid<T>, seconds: i64) -> Point<usize> {
+        space
+            .offset(self.position.into(), self.velocity.into(), seconds)
+            .into()
+    }
 
-        if self.position.0 < 0 {
-            self.position.0 += MAX_TILES_X;
-        }
-        if self.position.0 >= MAX_TILES_X {
-            self.position.0 -= MAX_TILES_X;
-        }
-        if self.position.1 < 0 {
-            self.position.1 += MAX_TILES_Y;
-        }
-        if self.position.1 >= MAX_TILES_Y {
-            self.position.1 -= MAX_TILES_Y;
-        }
+    fn next<T>(&mut self, space: &TorusGrid<T>) {
+        self.position = self.after(space, 1);
     }
 }
 
-#[aoc(day14, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut robots = input
+fn parse_robots(input: &str, size: (usize, usize)) -> Result<Vec<Robot>, ParseInputError> {
+    let space = TorusGrid::new_default(size, ());
+    input
         .lines()
-        .map(|line| Robot::from_input(line))
-        .collect_vec();
+        .map(|line| Robot::from_input(input, line, &space))
+        .collect()
+}
 
-    for _ in 0..100 {
-        for robot in robots.iter_mut() {
-            robot.next();
-        }
-    }
+fn safety_factor(robots: &[Robot], size: (usize, usize)) -> usize {
+    let mut space = TorusGrid::new_default(size, 0);
 
-    let mut count = vec![vec![0; 2]; 2];
-
-    for robot in robots.iter_mut() {
-        if robot.position.0 < MAX_TILES_X / 2 && robot.position.1 < MAX_TILES_Y / 2 {
-            count[0][0] += 1;
-        } else if robot.position.0 > MAX_TILES_X / 2 && robot.position.1 < MAX_TILES_Y / 2 {
-            count[0][1] += 1;
-        } else if robot.position.0 < MAX_TILES_X / 2 && robot.position.1 > MAX_TILES_Y / 2 {
-            count[1][0] += 1;
-        } else if robot.position.0 > MAX_TILES_X / 2 && robot.position.1 > MAX_TILES_Y / 2 {
-            count[1][1] += 1;
-        }
+    for robot in robots {
+        let (x, y) = robot.after(&space, 100).into();
+        space.grid[(x, y)] += 1;
     }
 
-    count[0][0] * count[0][1] * count[1][0] * count[1][1]
+    space.quadrant_counts().iter().product()
 }
 
-const DIRS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Down,
-    Direction::Left,
-    Direction::Right,
-];
-
 fn count_connections(map: &Vec2D<char>) -> u32 {
     let mut count = 0;
     let mut visited = HashSet::new();
 
-    for i in 0..MAX_TILES_Y as usize {
-        for j in 0..MAX_TILES_X as usize {
-            if *map.get(i, j).unwrap() == '#' && !visited.contains(&(i, j)) {
+    for i in 0..map.size.0 {
+        for j in 0..map.size.1 {
+            if map[(i, j)] == '#' && !visited.contains(&(i, j)) {
                 count += 1;
                 visited.insert((i, j));
 
                 let mut stack = vec![(i, j)];
                 while let Some((x, y)) = stack.pop() {
-                    for dir in DIRS {
-                        if let Some((nx, ny)) = dir.checked_add((x, y)) {
-                            if let Some(c) = map.get(nx, ny) {
-                                if *c == '#' && !visited.contains(&(nx, ny)) {
-                                    visited.insert((nx, ny));
-                                    stack.push((nx, ny));
-                                }
-                            }
+                    for ((nx, ny), c) in map.neighbors4((x, y)) {
+                        if *c == '#' && !visited.contains(&(nx, ny)) {
+                            visited.insert((nx, ny));
+                            stack.push((nx, ny));
                         }
                     }
                 }
@@ -119,57 +88,75 @@ fn count_connections(map: &Vec2D<char>) -> u32 {
     count
 }
 
-#[aoc(day14, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut robots = input
-        .lines()
-        .map(|line| Robot::from_input(line))
-        .collect_vec();
+pub struct Day14;
 
-    for i in 0..1000000 {
-        for robot in robots.iter_mut() {
-            robot.next();
-        }
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Parsed<'a> = Vec<Robot>;
+    type Answer1 = usize;
+    type Answer2 = i32;
 
-        let mut map = Vec2D::new_default((MAX_TILES_Y as usize, MAX_TILES_X as usize), '.');
-        for robot in robots.iter() {
-            map.set(robot.position.0 as usize, robot.position.1 as usize, '#');
-        }
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        parse_robots(input, SPACE_SIZE)
+    }
+
+    fn part1(robots: &Self::Parsed<'_>) -> Result<usize, Box<dyn Error>> {
+        Ok(safety_factor(robots, SPACE_SIZE))
+    }
 
-        // Filter candidates, assuming to construct a christmas tree, there must
-        // be a group with many connected cell
-        let connections = count_connections(&map);
-        if connections < 300 {
-            println!("{}", map);
-            println!("{} {}", i + 1, connections);
+    fn part2(robots: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut robots = robots.clone();
+        let empty = TorusGrid::new_default(SPACE_SIZE, '.');
 
-            return i + 1;
+        for i in 0..1000000 {
+            let mut space = empty.clone();
+            for robot in robots.iter_mut() {
+                robot.next(&space);
+                space.grid.set(robot.position.x, robot.position.y, '#');
+            }
+
+            // Filter candidates, assuming to construct a christmas tree, there must
+            // be a group with many connected cell
+            let connections = count_connections(&space.grid);
+            if connections < 300 {
+                return Ok(i + 1);
+            }
         }
+
+        Ok(0)
     }
+}
+
+#[aoc(day14, part1)]
+pub fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    Day14::solve_part1(input)
+}
 
-    0
+#[aoc(day14, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day14::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
+    const SAMPLE_INPUT: &str = include_str!("../fixtures/2024/day14.sample1.txt");
 
     use super::*;
 
     #[test]
     fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 12);
+        let robots = parse_robots(SAMPLE_INPUT, (7, 11)).unwrap();
+        assert_eq!(safety_factor(&robots, (7, 11)), 12);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = Day14::parse_input("p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!(err.day, Some(14));
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.expected, "4 integers");
+
+        let err = Day14::parse_input("p=0,4 v=3,-99999999999999999999\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(11)));
     }
 }
@@ -1,42 +1,46 @@
-use crate::{direction::Direction, vec2d::Vec2D};
+use std::error::Error;
+
+use crate::{
+    direction::Direction, errors::ParseInputError, parse, solutions::Solution, vec2d::Vec2D,
+};
 
 fn move_stone(
     map: &mut Vec2D<char>,
     robot: (usize, usize),
     direction: Direction,
 ) -> (usize, usize) {
-    let mut pos = robot.clone();
-    let mut first_empty_space = robot.clone();
+    let mut pos = robot;
+    let mut first_empty_space;
     loop {
-        if let Some(next) = direction.checked_add(pos) {
-            if let Some(c) = map.get(next.0, next.1) {
-                match *c {
-                    '.' => {
-                        first_empty_space = next;
-                        break;
-                    }
-                    '#' => {
-                        break;
-                    }
-                    _ => {
-                        pos = next;
-                    }
-                }
+        // Walking off the map is as good as hitting a wall
+        let Some(next) = direction
+            .checked_add(pos)
+            .filter(|next| map.get(next.0, next.1).is_some())
+        else {
+            return robot;
+        };
+        match map[next] {
+            '.' => {
+                first_empty_space = next;
+                break;
+            }
+            '#' => {
+                return robot;
+            }
+            _ => {
+                pos = next;
             }
         }
     }
 
-    let mut previous = robot.clone();
+    let mut previous = robot;
     while robot != first_empty_space {
         if let Some(next) = direction.opposite().checked_add(first_empty_space) {
             if let Some(c) = map.get(next.0, next.1) {
-                let current = map
-                    .get(first_empty_space.0, first_empty_space.1)
-                    .unwrap()
-                    .clone();
+                let current = map[first_empty_space];
                 map.set(first_empty_space.0, first_empty_space.1, *c);
                 map.set(next.0, next.1, current);
-                previous = first_empty_space.clone();
+                previous = first_empty_space;
                 first_empty_space = next;
             }
         }
@@ -45,74 +49,28 @@ fn move_stone(
     previous
 }
 
-#[aoc(day15, part1)]
-pub fn part1(input: &str) -> i32 {
-    let (map_input, commands_input) = input.split_once("\n\n").unwrap();
-    let mut map = Vec2D::new_chars(map_input);
-    let mut robot = map.find_first('@').unwrap();
-
-    for cmd in commands_input.chars() {
-        let direction = match cmd {
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            _ => None,
-        };
-        if let Some(direction) = direction {
-            robot = move_stone(&mut map, robot, direction);
-        }
-        // println!("{}", map);
-    }
-
-    map.find_all('O')
-        .iter()
-        .map(|(x, y)| (*x as i32) * 100 + *y as i32)
-        .sum()
-}
-
 struct Map {
     map: Vec2D<char>,
     robot: (usize, usize),
 }
 
 impl Map {
-    pub fn from_input(input: &str) -> Self {
-        let x = input.lines().count();
-        let y = input.lines().next().unwrap().len() * 2;
-        let mut map = Vec2D::<char>::new_default((x, y), ' ');
-
-        for (i, line) in input.lines().enumerate() {
-            for (j, c) in line.chars().enumerate() {
-                match c {
-                    '@' => {
-                        map.set(i, j * 2, '@');
-                        map.set(i, j * 2 + 1, '.');
-                    }
-                    '#' => {
-                        map.set(i, j * 2, '#');
-                        map.set(i, j * 2 + 1, '#');
-                    }
-                    'O' => {
-                        map.set(i, j * 2, '[');
-                        map.set(i, j * 2 + 1, ']');
-                    }
-                    '.' => {
-                        map.set(i, j * 2, '.');
-                        map.set(i, j * 2 + 1, '.');
-                    }
-                    _ => {}
-                }
-            }
-        }
+    pub fn new(map: &Vec2D<char>) -> Self {
+        let map = map.widen(2, |c| match c {
+            '@' => ['@', '.'],
+            '#' => ['#', '#'],
+            'O' => ['[', ']'],
+            '.' => ['.', '.'],
+            _ => [' ', ' '],
+        });
 
-        let robot = map.find_first('@').unwrap();
+        let robot = map.find_first('@').unwrap_or_default();
 
         Self { map, robot }
     }
 
     pub fn try_move(&mut self, position: (usize, usize), direction: Direction) -> bool {
-        let current = self.map.get(position.0, position.1).unwrap();
+        let current = &self.map[position];
         // println!("Try move: {:?} {:?} ({})", position, direction, current);
         if *current == '@' {
             if let Some(next) = direction.checked_add(position) {
@@ -120,8 +78,7 @@ impl Map {
                     Some('.') => true,
                     Some('[') => self.try_move(next, direction),
                     Some(']') => self.try_move((next.0, next.1 - 1), direction),
-                    Some('#') => false,
-                    _ => panic!("Invalid state"),
+                    _ => false,
                 };
             }
             return false;
@@ -136,18 +93,16 @@ impl Map {
                     (Some(next_left), Some(next_right)) => {
                         let left_movable = match self.map.get(next_left.0, next_left.1) {
                             Some('.') => true,
-                            Some('#') => false,
                             Some('[') => self.try_move(next_left, direction),
                             Some(']') => self.try_move((next_left.0, next_left.1 - 1), direction),
-                            _ => panic!("Invalid character"),
+                            _ => false,
                         };
 
                         let right_movable = match self.map.get(next_right.0, next_right.1) {
                             Some('.') => true,
-                            Some('#') => false,
                             Some('[') => self.try_move(next_right, direction),
                             Some(']') => true,
-                            _ => panic!("Invalid character"),
+                            _ => false,
                         };
 
                         left_movable && right_movable
@@ -158,29 +113,27 @@ impl Map {
             Direction::Left => match direction.checked_add(position) {
                 Some(next_left) => match self.map.get(next_left.0, next_left.1) {
                     Some('.') => true,
-                    Some('#') => false,
-                    Some('[') => panic!("Impossible state"),
                     Some(']') => self.try_move((next_left.0, next_left.1 - 1), direction),
-                    _ => unimplemented!(),
+                    _ => false,
                 },
                 _ => false,
             },
             Direction::Right => match direction.checked_add((position.0, position.1 + 1)) {
                 Some(next_left) => match self.map.get(next_left.0, next_left.1) {
                     Some('.') => true,
-                    Some('#') => false,
-                    Some(']') => panic!("Impossible state"),
                     Some('[') => self.try_move(next_left, direction),
-                    _ => unimplemented!(),
+                    _ => false,
                 },
                 _ => false,
             },
-            _ => panic!("Invalid direction"),
+            _ => false,
         }
     }
 
+    /// Moves the robot or box at `position`. Only valid after `try_move`
+    /// has accepted the same move.
     pub fn commit_move(&mut self, position: (usize, usize), direction: Direction) {
-        let current = self.map.get(position.0, position.1).unwrap();
+        let current = &self.map[position];
         // println!(
         //     "Committing move: {:?} {:?} ({})",
         //     position, direction, current
@@ -191,7 +144,7 @@ impl Map {
                     Some('.') => {}
                     Some('[') => self.commit_move(next, direction),
                     Some(']') => self.commit_move((next.0, next.1 - 1), direction),
-                    _ => panic!("Invalid move"),
+                    _ => unreachable!("move was not checked with try_move"),
                 }
                 self.map.set(next.0, next.1, '@');
                 self.map.set(position.0, position.1, '.');
@@ -215,7 +168,7 @@ impl Map {
                         Some(']') => {
                             self.commit_move((next_left.0, next_left.1 - 1), direction);
                         }
-                        _ => panic!("Invalid move"),
+                        _ => unreachable!("move was not checked with try_move"),
                     };
 
                     self.map.set(next_left.0, next_left.1, '[');
@@ -227,13 +180,13 @@ impl Map {
                             self.commit_move(next_right, direction);
                         }
                         Some(']') => {}
-                        _ => panic!("Invalid move"),
+                        _ => unreachable!("move was not checked with try_move"),
                     };
 
                     self.map.set(next_right.0, next_right.1, ']');
                     self.map.set(position.0, position.1 + 1, '.');
                 }
-                _ => panic!("Invalid move"),
+                _ => unreachable!("move was not checked with try_move"),
             },
 
             Direction::Left => match direction.checked_add(position) {
@@ -243,14 +196,14 @@ impl Map {
                         Some(']') => {
                             self.commit_move((next_left.0, next_left.1 - 1), direction);
                         }
-                        _ => panic!("Invalid move"),
+                        _ => unreachable!("move was not checked with try_move"),
                     }
 
                     self.map.set(next_left.0, next_left.1, '[');
                     self.map.set(position.0, position.1, ']');
                     self.map.set(position.0, position.1 + 1, '.');
                 }
-                _ => panic!("Invalid move"),
+                _ => unreachable!("move was not checked with try_move"),
             },
             Direction::Right => match direction.checked_add((position.0, position.1 + 1)) {
                 Some(next_left) => {
@@ -259,107 +212,132 @@ impl Map {
                         Some('[') => {
                             self.commit_move(next_left, direction);
                         }
-                        _ => panic!("Invalid move"),
+                        _ => unreachable!("move was not checked with try_move"),
                     }
 
                     self.map.set(next_left.0, next_left.1, ']');
                     self.map.set(position.0, position.1 + 1, '[');
                     self.map.set(position.0, position.1, '.');
                 }
-                _ => panic!("Invalid move"),
+                _ => unreachable!("move was not checked with try_move"),
             },
-            _ => panic!("Invalid move"),
+            _ => unreachable!("move was not checked with try_move"),
         }
     }
 }
 
-#[aoc(day15, part2)]
-pub fn part2(input: &str) -> i32 {
-    let (map_input, commands_input) = input.split_once("\n\n").unwrap();
-    let mut map = Map::from_input(map_input);
-    let commands = commands_input.trim_end().chars();
-
-    // println!("{}", map.map);
-
-    for cmd in commands {
-        let direction = match cmd {
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            _ => None,
-        };
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Parsed<'a> = (Vec2D<char>, Vec<Direction>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let [map_input, commands_input] = parse::sections_n(input, input)?;
+        let map = Vec2D::parse(map_input, |c| {
+            matches!(c, '#' | '.' | 'O' | '@').then_some(c)
+        })?;
+        if map.find_all('@').len() != 1 {
+            return Err(ParseInputError::new("exactly one robot '@'")
+                .with_found(format!("{}", map.find_all('@').len())));
+        }
 
-        // println!("Trying to move: {:?}", direction);
+        let commands = commands_input
+            .char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| match c {
+                '^' => Ok(Direction::Up),
+                'v' => Ok(Direction::Down),
+                '<' => Ok(Direction::Left),
+                '>' => Ok(Direction::Right),
+                _ => Err(ParseInputError::new("a move ('^', 'v', '<' or '>')")
+                    .at_str(input, &commands_input[i..i + c.len_utf8()])),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((map, commands))
+    }
 
-        if let Some(direction) = direction {
-            if map.try_move(map.robot, direction) {
-                map.commit_move(map.robot, direction);
-            }
+    fn part1((map, commands): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut map = map.clone();
+        let mut robot = map.find_first('@').unwrap_or_default();
+
+        for direction in commands {
+            robot = move_stone(&mut map, robot, *direction);
         }
 
+        Ok(map
+            .find_all('O')
+            .iter()
+            .map(|(x, y)| (*x as i32) * 100 + *y as i32)
+            .sum())
+    }
+
+    fn part2((map, commands): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut map = Map::new(map);
+
         // println!("{}", map.map);
-        // wait for input
-        // let mut input = String::new();
-        // std::io::stdin().read_line(&mut input).unwrap();
+
+        for direction in commands {
+            // println!("Trying to move: {:?}", direction);
+
+            if map.try_move(map.robot, *direction) {
+                map.commit_move(map.robot, *direction);
+            }
+
+            // println!("{}", map.map);
+            // wait for input
+            // let mut input = String::new();
+            // std::io::stdin().read_line(&mut input).unwrap();
+        }
+
+        Ok(map
+            .map
+            .find_all('[')
+            .iter()
+            .map(|(x, y)| (*x as i32) * 100 + *y as i32)
+            .sum())
     }
+}
 
-    map.map
-        .find_all('[')
-        .iter()
-        .map(|(x, y)| (*x as i32) * 100 + *y as i32)
-        .sum()
+#[aoc(day15, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day15::solve_part1(input)
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day15::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-    const SAMPLE_INPUT_2: &str = "\
-#######
-#...#.#
-#.....#
-#..OO@#
-#..O..#
-#.....#
-#######
-
-<vv<<^^<<^^";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 10092);
-    }
+    fn test_invalid_input() {
+        let err = Day15::parse_input("#####\n#.@O#\n#####\n").unwrap_err();
+        assert_eq!(err.day, Some(15));
+        assert_eq!(err.expected, "2 sections separated by blank lines");
 
-    #[test]
-    fn test_sample_part_2_simple_test() {
-        assert_eq!(part2(SAMPLE_INPUT_2), 618);
+        let err = Day15::parse_input("#####\n#.@X#\n#####\n\n<<").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(4)));
+
+        let err = Day15::parse_input("#####\n#.@O#\n#####\n\n<<\n>x").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(6), Some(2)));
+
+        let err = Day15::parse_input("#####\n#.@O#\n#####\n\n<U").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(5), Some(2)));
+
+        let err = Day15::parse_input("#####\n#..O#\n#####\n\n<<").unwrap_err();
+        assert_eq!(err.found.as_deref(), Some("0"));
     }
 
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 9021);
+    fn test_unwalled_map() {
+        assert_eq!(part1("..@O\n\n>>><<<<").unwrap(), 3);
+        assert_eq!(part2("..@O\n\n>>><<<<^").unwrap(), 6);
     }
 }
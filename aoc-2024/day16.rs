@@ -1,16 +1,22 @@
 use std::{
     cmp::Ordering,
     collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
 };
 
-use crate::{direction::Direction, vec2d::Vec2D};
+use crate::{
+    direction::{Direction, DirectionSet},
+    errors::ParseInputError,
+    solutions::Solution,
+    vec2d::Vec2D,
+};
+
+type Maze = (Vec2D<char>, (usize, usize), (usize, usize));
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Down,
-    Direction::Left,
-    Direction::Right,
-];
+/// One step forward plus 1000 for every quarter-turn before it.
+fn move_cost(from: Direction, to: Direction) -> i32 {
+    1 + 1000 * Direction::turns_between(from, to).unwrap() as i32
+}
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct State {
@@ -67,192 +73,180 @@ impl PartialOrd for Node {
     }
 }
 
-#[aoc(day16, part1)]
-pub fn part1(input: &str) -> i32 {
-    let map = Vec2D::new_chars(input);
-    let start = map.find_first('S').unwrap();
-    let end = map.find_first('E').unwrap();
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Parsed<'a> = Maze;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let map = Vec2D::parse(input, |c| matches!(c, '#' | '.' | 'S' | 'E').then_some(c))?;
+        let start = map
+            .find_first('S')
+            .ok_or_else(|| ParseInputError::new("a start tile 'S'").with_found("none"))?;
+        let end = map
+            .find_first('E')
+            .ok_or_else(|| ParseInputError::new("an end tile 'E'").with_found("none"))?;
+        Ok((map, start, end))
+    }
 
-    let mut visited = HashSet::<((usize, usize), Direction)>::new();
+    fn part1(&(ref map, start, end): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut visited = Vec2D::new_default(map.size, DirectionSet::new());
 
-    let mut heap = BinaryHeap::new();
+        let mut heap = BinaryHeap::new();
 
-    heap.push(Node::new(0, start, Direction::Right));
+        heap.push(Node::new(0, start, Direction::Right));
 
-    let mut min = i32::MAX;
+        let mut min = i32::MAX;
 
-    while let Some(Node {
-        cost,
-        current: State {
-            position,
-            direction,
-        },
-        previous: _,
-    }) = heap.pop()
-    {
-        if position == end {
-            min = min.min(cost);
-            continue;
-        }
+        while let Some(Node {
+            cost,
+            current: State {
+                position,
+                direction,
+            },
+            previous: _,
+        }) = heap.pop()
+        {
+            if position == end {
+                min = min.min(cost);
+                continue;
+            }
 
-        if visited.contains(&(position, direction)) {
-            continue;
-        }
+            if !visited[position].insert(direction) {
+                continue;
+            }
 
-        visited.insert((position, direction));
-
-        let mut next_direction = direction.clone();
-        for i in 0..4 {
-            if let Some(next) = next_direction.checked_add(position) {
-                match map.get(next.0, next.1) {
-                    Some('.') | Some('E') => {
-                        heap.push(Node::new(
-                            cost + match i {
-                                0 => 1,
-                                1 => 1001,
-                                2 => 2001,
-                                3 => 1001,
-                                _ => unreachable!(),
-                            },
-                            next,
-                            next_direction.clone(),
-                        ));
+            for next_direction in Direction::CARDINAL {
+                if let Some(next) = next_direction.checked_add(position) {
+                    match map.get(next.0, next.1) {
+                        Some('.') | Some('E') => {
+                            heap.push(Node::new(
+                                cost + move_cost(direction, next_direction),
+                                next,
+                                next_direction,
+                            ));
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
-            next_direction = next_direction.clockwise();
         }
+
+        Ok(min)
     }
 
-    min
-}
+    fn part2(&(ref map, start, end): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut min_cost = HashMap::<State, i32>::new();
+        let mut backtrack = HashMap::<State, Vec<State>>::new();
 
-#[aoc(day16, part2)]
-pub fn part2(input: &str) -> i32 {
-    let map = Vec2D::new_chars(input);
-    let start = map.find_first('S').unwrap();
-    let end = map.find_first('E').unwrap();
-
-    let mut min_cost = HashMap::<State, i32>::new();
-    let mut backtrack = HashMap::<State, Vec<State>>::new();
-
-    let mut heap = BinaryHeap::new();
-
-    heap.push(Node::new(0, start, Direction::Right));
-
-    while let Some(Node {
-        cost,
-        current,
-        previous,
-    }) = heap.pop()
-    {
-        let min_cost_at_position = min_cost.get(&current).unwrap_or(&i32::MAX);
-
-        if *min_cost_at_position < cost {
-            continue;
-        } else if *min_cost_at_position == cost {
-            backtrack.get_mut(&current).map(|v| v.push(previous));
-            continue;
-        } else if *min_cost_at_position == i32::MAX {
-            min_cost.insert(current, cost);
-
-            backtrack.insert(current, Vec::new());
-            backtrack.get_mut(&current).map(|v| v.push(previous));
-        } else {
-            unreachable!()
-        }
+        let mut heap = BinaryHeap::new();
 
-        if current.position == end {
-            continue;
-        }
+        heap.push(Node::new(0, start, Direction::Right));
+
+        while let Some(Node {
+            cost,
+            current,
+            previous,
+        }) = heap.pop()
+        {
+            let min_cost_at_position = min_cost.get(&current).unwrap_or(&i32::MAX);
+
+            if *min_cost_at_position < cost {
+                continue;
+            } else if *min_cost_at_position == cost {
+                if let Some(v) = backtrack.get_mut(&current) {
+                    v.push(previous);
+                }
+                continue;
+            } else if *min_cost_at_position == i32::MAX {
+                min_cost.insert(current, cost);
+
+                backtrack.insert(current, vec![previous]);
+            } else {
+                unreachable!()
+            }
 
-        let mut next_direction = current.direction.clone();
-        for i in 0..4 {
-            if let Some(next) = next_direction.checked_add(current.position) {
-                match map.get(next.0, next.1) {
-                    Some('.') | Some('E') => {
-                        heap.push(Node::with_previous(
-                            cost + match i {
-                                0 => 1,
-                                1 => 1001,
-                                2 => 2001,
-                                3 => 1001,
-                                _ => unreachable!(),
-                            },
-                            State::new(next, next_direction.clone()),
-                            current,
-                        ));
+            if current.position == end {
+                continue;
+            }
+
+            for next_direction in Direction::CARDINAL {
+                if let Some(next) = next_direction.checked_add(current.position) {
+                    match map.get(next.0, next.1) {
+                        Some('.') | Some('E') => {
+                            heap.push(Node::with_previous(
+                                cost + move_cost(current.direction, next_direction),
+                                State::new(next, next_direction),
+                                current,
+                            ));
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
-            next_direction = next_direction.clockwise();
         }
-    }
 
-    let mut stack = Vec::<State>::new();
+        let mut stack = Vec::<State>::new();
 
-    let min = DIRECTIONS
-        .iter()
-        .map(|d| min_cost.get(&State::new(end, *d)).unwrap_or(&i32::MAX))
-        .min()
-        .unwrap();
+        let min = Direction::CARDINAL
+            .iter()
+            .map(|d| min_cost.get(&State::new(end, *d)).unwrap_or(&i32::MAX))
+            .min()
+            .unwrap();
 
-    for d in DIRECTIONS.iter() {
-        match min_cost.get(&State::new(end, *d)) {
-            Some(c) if c == min => {
-                stack.push(State::new(end, *d));
+        for d in Direction::CARDINAL.iter() {
+            match min_cost.get(&State::new(end, *d)) {
+                Some(c) if c == min => {
+                    stack.push(State::new(end, *d));
+                }
+                _ => {}
             }
-            _ => {}
         }
-    }
 
-    let mut visit = HashSet::<(usize, usize)>::new();
+        let mut visit = HashSet::<(usize, usize)>::new();
 
-    while let Some(state) = stack.pop() {
-        visit.insert(state.position);
+        while let Some(state) = stack.pop() {
+            visit.insert(state.position);
 
-        if state.position == start {
-            continue;
-        }
+            if state.position == start {
+                continue;
+            }
 
-        for prev_state in backtrack.get(&state).unwrap() {
-            stack.push(*prev_state);
+            for prev_state in backtrack.get(&state).unwrap() {
+                stack.push(*prev_state);
+            }
         }
+
+        Ok(visit.len() as i32)
     }
+}
+
+#[aoc(day16, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day16::solve_part1(input)
+}
 
-    visit.len() as i32
+#[aoc(day16, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day16::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 7036);
-    }
-
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 45);
+    fn test_invalid_input() {
+        let err = Day16::parse_input("####\n#S.#\n####").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input for day 16: expected an end tile 'E', found none"
+        );
+
+        let err = Day16::parse_input("####\n#S?E\n####").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
     }
 }
@@ -5,7 +5,7 @@ use z3::{
     Config, Context, Optimize, SatResult,
 };
 
-use crate::errors::ParseInputError;
+use crate::{errors::ParseInputError, parse, solutions::Solution};
 
 #[derive(Debug, PartialEq)]
 enum State {
@@ -24,19 +24,8 @@ struct Program {
 }
 
 impl Program {
-    pub fn from_input(input: &str) -> Result<Self, ParseInputError> {
-        let (registers_input, program_input) = input.split_once("\n\n").ok_or(ParseInputError)?;
-        let mut lines = registers_input.lines();
-        let register_a = Self::parse_register("A", lines.next())?;
-        let register_b = Self::parse_register("B", lines.next())?;
-        let register_c = Self::parse_register("C", lines.next())?;
-        let program = program_input
-            .replace("Program: ", "")
-            .split(",")
-            .map(|num| num.parse::<u32>())
-            .collect::<Result<Vec<u32>, _>>()
-            .or(Err(ParseInputError))?;
-        Ok(Self {
+    pub fn new([register_a, register_b, register_c]: [u32; 3], program: Vec<u32>) -> Self {
+        Self {
             program,
             instruction_pointer: 0,
             register_a,
@@ -44,32 +33,22 @@ impl Program {
             register_c,
             state: State::Running,
             output: Vec::new(),
-        })
-    }
-
-    fn parse_register(register_name: &str, line: Option<&str>) -> Result<u32, ParseInputError> {
-        match line {
-            Some(line) => line
-                .replace(&"Register *: ".replace("*", register_name), "")
-                .parse::<u32>()
-                .map_err(|_| ParseInputError),
-            None => Err(ParseInputError),
         }
     }
 }
 
 impl Program {
-    pub fn tick(&mut self) {
+    pub fn tick(&mut self) -> Result<(), Box<dyn Error>> {
         if self.state == State::Halted {
-            return;
+            return Ok(());
         }
 
         match self.program.get(self.instruction_pointer) {
             Some(opcode) => {
-                let operator = self.program[self.instruction_pointer + 1];
+                let operator = operand(&self.program, self.instruction_pointer)?;
                 match opcode {
                     0 => {
-                        self.register_a >>= self.combo_operand(operator);
+                        self.register_a >>= self.combo_operand(operator)?;
                         self.instruction_pointer += 2;
                     }
                     1 => {
@@ -77,7 +56,7 @@ impl Program {
                         self.instruction_pointer += 2;
                     }
                     2 => {
-                        self.register_b = self.combo_operand(operator) % 8;
+                        self.register_b = self.combo_operand(operator)? % 8;
                         self.instruction_pointer += 2;
                     }
                     3 => {
@@ -92,56 +71,36 @@ impl Program {
                         self.instruction_pointer += 2;
                     }
                     5 => {
-                        self.output.push(self.combo_operand(operator) & 7);
+                        self.output.push(self.combo_operand(operator)? & 7);
                         self.instruction_pointer += 2;
                     }
                     6 => {
-                        self.register_b = self.register_a >> self.combo_operand(operator);
+                        self.register_b = self.register_a >> self.combo_operand(operator)?;
                         self.instruction_pointer += 2;
                     }
                     7 => {
-                        self.register_c = self.register_a >> self.combo_operand(operator);
+                        self.register_c = self.register_a >> self.combo_operand(operator)?;
                         self.instruction_pointer += 2;
                     }
-                    _ => unreachable!(),
+                    _ => return Err(format!("Unknown opcode {}", opcode).into()),
                 }
             }
             None => {
                 self.state = State::Halted;
-                return;
             }
         }
+        Ok(())
     }
 
-    fn combo_operand(&self, operator: u32) -> u32 {
+    fn combo_operand(&self, operator: u32) -> Result<u32, Box<dyn Error>> {
         match operator {
-            0..=3 => operator,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[aoc(day17, part1)]
-pub fn part1(input: &str) -> Result<String, Box<dyn Error>> {
-    let mut program = Program::from_input(input)?;
-
-    loop {
-        if program.state == State::Halted {
-            break;
+            0..=3 => Ok(operator),
+            4 => Ok(self.register_a),
+            5 => Ok(self.register_b),
+            6 => Ok(self.register_c),
+            _ => Err(format!("Invalid combo operand {}", operator).into()),
         }
-        program.tick();
     }
-
-    // Vec<u32> to string with comma
-    Ok(program
-        .output
-        .iter()
-        .map(|num| num.to_string())
-        .collect::<Vec<String>>()
-        .join(","))
 }
 
 #[derive(Debug)]
@@ -160,60 +119,42 @@ struct ProgramZ3<'ctx> {
 }
 
 impl<'ctx> ProgramZ3<'ctx> {
-    pub fn from_input(input: &str, ctx: &'ctx Context) -> Result<Self, ParseInputError> {
-        let (registers_input, program_input) = input.split_once("\n\n").ok_or(ParseInputError)?;
-        let mut lines = registers_input.lines();
-        lines.next();
-        let register_b = Self::parse_register("B", lines.next())?;
-        let register_c = Self::parse_register("C", lines.next())?;
-        let program = program_input
-            .replace("Program: ", "")
-            .split(",")
-            .map(|num| num.parse::<u32>())
-            .collect::<Result<Vec<u32>, _>>()
-            .or(Err(ParseInputError))?;
-
-        Ok(Self {
+    pub fn new(
+        [_, register_b, register_c]: [u32; 3],
+        program: Vec<u32>,
+        ctx: &'ctx Context,
+    ) -> Self {
+        Self {
             program,
             instruction_pointer: 0,
-            register_a: ast::BV::new_const(&ctx, "a", 64),
-            register_b: ast::BV::from_i64(&ctx, register_b as i64, 64),
-            register_c: ast::BV::from_i64(&ctx, register_c as i64, 64),
+            register_a: ast::BV::new_const(ctx, "a", 64),
+            register_b: ast::BV::from_i64(ctx, register_b as i64, 64),
+            register_c: ast::BV::from_i64(ctx, register_c as i64, 64),
             state: State::Running,
             assumptions: vec![],
-            ctx: &ctx,
+            ctx,
             n_out_commands: 0,
-        })
-    }
-
-    fn parse_register(register_name: &str, line: Option<&str>) -> Result<u32, ParseInputError> {
-        match line {
-            Some(line) => line
-                .replace(&"Register *: ".replace("*", register_name), "")
-                .parse::<u32>()
-                .map_err(|_| ParseInputError),
-            None => Err(ParseInputError),
         }
     }
 }
 
 impl<'ctx> ProgramZ3<'ctx> {
-    fn tick(&mut self) {
+    fn tick(&mut self) -> Result<(), Box<dyn Error>> {
         if self.state == State::Halted {
-            return;
+            return Ok(());
         }
 
         match self.program.get(self.instruction_pointer) {
             Some(opcode) => {
-                let operator = self.program[self.instruction_pointer + 1];
+                let operator = operand(&self.program, self.instruction_pointer)?;
                 match opcode {
                     0 => {
-                        self.register_a = self.register_a.bvlshr(&self.combo_operand(operator));
+                        self.register_a = self.register_a.bvlshr(&self.combo_operand(operator)?);
                         self.instruction_pointer += 2;
                     }
                     1 => {
                         self.register_b = self.register_b.bvxor(&ast::BV::from_i64(
-                            &self.ctx,
+                            self.ctx,
                             operator as i64,
                             64,
                         ));
@@ -221,21 +162,21 @@ impl<'ctx> ProgramZ3<'ctx> {
                     }
                     2 => {
                         self.register_b = self
-                            .combo_operand(operator)
-                            .bvand(&ast::BV::from_i64(&self.ctx, 7, 64));
+                            .combo_operand(operator)?
+                            .bvand(&ast::BV::from_i64(self.ctx, 7, 64));
                         self.instruction_pointer += 2;
                     }
                     3 => {
                         if self.n_out_commands < self.program.len() {
                             let constraint = self
                                 .register_a
-                                ._eq(&ast::BV::from_i64(&self.ctx, 0, 64))
+                                ._eq(&ast::BV::from_i64(self.ctx, 0, 64))
                                 .not();
                             self.assumptions.push(constraint);
                             self.instruction_pointer = operator as usize;
                         } else {
                             let constraint =
-                                self.register_a._eq(&ast::BV::from_i64(&self.ctx, 0, 64));
+                                self.register_a._eq(&ast::BV::from_i64(self.ctx, 0, 64));
                             self.assumptions.push(constraint);
                             self.instruction_pointer += 2;
                         }
@@ -245,105 +186,205 @@ impl<'ctx> ProgramZ3<'ctx> {
                         self.instruction_pointer += 2;
                     }
                     5 => {
-                        let expected = self.program[self.n_out_commands];
+                        let expected = *self
+                            .program
+                            .get(self.n_out_commands)
+                            .ok_or("The program outputs more values than it has")?;
                         let constraint = self
-                            .combo_operand(operator)
-                            .bvand(&ast::BV::from_i64(&self.ctx, 7, 64))
-                            ._eq(&ast::BV::from_i64(&self.ctx, expected as i64, 64));
+                            .combo_operand(operator)?
+                            .bvand(&ast::BV::from_i64(self.ctx, 7, 64))
+                            ._eq(&ast::BV::from_i64(self.ctx, expected as i64, 64));
 
                         self.assumptions.push(constraint);
                         self.instruction_pointer += 2;
                         self.n_out_commands += 1;
                     }
                     6 => {
-                        self.register_b = self.register_a.bvlshr(&self.combo_operand(operator));
+                        self.register_b = self.register_a.bvlshr(&self.combo_operand(operator)?);
                         self.instruction_pointer += 2;
                     }
                     7 => {
-                        self.register_c = self.register_a.bvlshr(&self.combo_operand(operator));
+                        self.register_c = self.register_a.bvlshr(&self.combo_operand(operator)?);
                         self.instruction_pointer += 2;
                     }
-                    _ => unreachable!(),
+                    _ => return Err(format!("Unknown opcode {}", opcode).into()),
                 }
             }
             None => {
                 self.state = State::Halted;
-                return;
             }
         }
+        Ok(())
     }
 
-    fn combo_operand(&self, operator: u32) -> ast::BV<'ctx> {
+    fn combo_operand(&self, operator: u32) -> Result<ast::BV<'ctx>, Box<dyn Error>> {
         match operator {
-            0..=3 => ast::BV::from_i64(&self.ctx, operator as i64, 64),
-            4 => self.register_a.clone(),
-            5 => self.register_b.clone(),
-            6 => self.register_c.clone(),
-            _ => unreachable!(),
+            0..=3 => Ok(ast::BV::from_i64(self.ctx, operator as i64, 64)),
+            4 => Ok(self.register_a.clone()),
+            5 => Ok(self.register_b.clone()),
+            6 => Ok(self.register_c.clone()),
+            _ => Err(format!("Invalid combo operand {}", operator).into()),
         }
     }
 }
 
-#[aoc(day17, part2)]
-pub fn part2(input: &str) -> Result<String, Box<dyn Error>> {
-    let cfg = Config::new();
-    let ctx = Context::new(&cfg);
+/// The operand of the instruction at `instruction_pointer`, which a jump can
+/// leave without one.
+fn operand(program: &[u32], instruction_pointer: usize) -> Result<u32, Box<dyn Error>> {
+    program
+        .get(instruction_pointer + 1)
+        .copied()
+        .ok_or_else(|| format!("The instruction at {} has no operand", instruction_pointer).into())
+}
+
+pub struct Day17;
 
-    let mut program = ProgramZ3::from_input(input, &ctx)?;
-    let opt = Optimize::new(&ctx);
-    let register_a = program.register_a.clone();
-    opt.minimize(&register_a);
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Parsed<'a> = ([u32; 3], Vec<u32>);
+    type Answer1 = String;
+    type Answer2 = String;
 
-    loop {
-        if program.state == State::Halted {
-            break;
+    /// Splits the input into the three register values and the program.
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let [registers_input, program_input] = parse::sections_n(input, input)?;
+
+        let mut lines = registers_input.lines();
+        let end_of_registers = &registers_input[registers_input.len()..];
+        let mut registers = [0; 3];
+        for (register, name) in registers.iter_mut().zip(["A", "B", "C"]) {
+            let line = lines.next().unwrap_or(end_of_registers);
+            let value = parse::field(input, line, &format!("Register {}", name))?;
+            *register = parse::token(input, value, "a register value")?;
+        }
+
+        let numbers = parse::field(input, program_input, "Program")?;
+        let tokens = numbers.split(',').collect::<Vec<_>>();
+        let program = tokens
+            .iter()
+            .map(|num| {
+                num.parse::<u32>()
+                    .ok()
+                    .filter(|num| *num < 8)
+                    .ok_or_else(|| ParseInputError::new("a 3-bit number").at_str(input, num))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        if program.len() % 2 == 1 {
+            return Err(ParseInputError::new("an operand after the last opcode")
+                .with_found("the end of the program")
+                .at_str(input, &numbers[numbers.len()..]));
+        }
+        // Combo operand 7 is reserved, so it's only valid for the literal
+        // operands of bxl, jnz and bxc.
+        for (instruction, tokens) in program.chunks(2).zip(tokens.chunks(2)) {
+            if matches!(instruction[0], 0 | 2 | 5 | 6 | 7) && instruction[1] == 7 {
+                return Err(
+                    ParseInputError::new("a combo operand from 0 to 6").at_str(input, tokens[1])
+                );
+            }
         }
-        program.tick();
+
+        Ok((registers, program))
     }
 
-    if let SatResult::Sat = opt.check(&program.assumptions) {
-        let model = opt.get_model().unwrap();
-        let a_val = model.eval(&register_a, false).unwrap();
-        return Ok(a_val.as_i64().unwrap().to_string());
+    fn part1((registers, program): &Self::Parsed<'_>) -> Result<String, Box<dyn Error>> {
+        let mut program = Program::new(*registers, program.clone());
+
+        loop {
+            if program.state == State::Halted {
+                break;
+            }
+            program.tick()?;
+        }
+
+        // Vec<u32> to string with comma
+        Ok(program
+            .output
+            .iter()
+            .map(|num| num.to_string())
+            .collect::<Vec<String>>()
+            .join(","))
     }
 
-    Err("No solution found".into())
-}
+    fn part2((registers, program): &Self::Parsed<'_>) -> Result<String, Box<dyn Error>> {
+        let cfg = Config::new();
+        let ctx = Context::new(&cfg);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut program = ProgramZ3::new(*registers, program.clone(), &ctx);
+        let opt = Optimize::new(&ctx);
+        let register_a = program.register_a.clone();
+        opt.minimize(&register_a);
 
-    const SAMPLE_INPUT_1: &str = "\
-Register A: 729
-Register B: 0
-Register C: 0
+        loop {
+            if program.state == State::Halted {
+                break;
+            }
+            program.tick()?;
+        }
 
-Program: 0,1,5,4,3,0";
+        if let SatResult::Sat = opt.check(&program.assumptions) {
+            let model = opt.get_model().ok_or("z3 found no model")?;
+            let a_val = model
+                .eval(&register_a, false)
+                .ok_or("z3 couldn't evaluate register A")?;
+            let a_val = a_val.as_i64().ok_or("Register A doesn't fit in an i64")?;
+            return Ok(a_val.to_string());
+        }
 
-    const SAMPLE_INPUT_2: &str = "\
-Register A: 10
-Register B: 0
-Register C: 0
+        Err("No solution found".into())
+    }
+}
 
-Program: 5,0,5,1,5,4";
+#[aoc(day17, part1)]
+pub fn part1(input: &str) -> Result<String, Box<dyn Error>> {
+    Day17::solve_part1(input)
+}
 
-    const SAMPLE_INPUT_3: &str = "\
-Register A: 2024
-Register B: 0
-Register C: 0
+#[aoc(day17, part2)]
+pub fn part2(input: &str) -> Result<String, Box<dyn Error>> {
+    Day17::solve_part2(input)
+}
 
-Program: 0,3,5,4,3,0";
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT_1).unwrap(), "4,6,3,5,6,3,5,2,1,0");
-        assert_eq!(part1(SAMPLE_INPUT_2).unwrap(), "0,1,2");
-        assert_eq!(part1(SAMPLE_INPUT_3).unwrap(), "4,2,5,6,7,7,7,7,3,1,0");
+    fn test_invalid_input() {
+        let error = Day17::parse_input("Register A: 1\nRegister B: x\nRegister C: 0\n\nProgram: 0")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), Some(13)));
+
+        let error =
+            Day17::parse_input("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,9")
+                .unwrap_err();
+        assert_eq!((error.line, error.column), (Some(5), Some(12)));
+
+        let error = Day17::parse_input("Register A: 1\n\nProgram: 0").unwrap_err();
+        assert_eq!(error.expected, "\"Register B: \"");
+        assert_eq!(error.line, Some(1));
     }
 
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT_3).unwrap(), "117440");
+    fn test_malformed_program() {
+        let error = Day17::parse_input("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0")
+            .unwrap_err();
+        assert_eq!(error.expected, "an operand after the last opcode");
+        assert_eq!((error.line, error.column), (Some(5), Some(11)));
+
+        let error =
+            Day17::parse_input("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,7")
+                .unwrap_err();
+        assert_eq!(error.expected, "a combo operand from 0 to 6");
+        assert_eq!((error.line, error.column), (Some(5), Some(12)));
+
+        // bxl takes a literal operand, so 7 is fine there.
+        assert_eq!(
+            part1("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,5").unwrap(),
+            "7"
+        );
+        // A jump to an odd address reads the last value as an opcode.
+        assert!(part1("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,1,0,0").is_err());
     }
 }
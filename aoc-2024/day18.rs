@@ -1,15 +1,8 @@
-use std::collections::{HashSet, VecDeque};
+use std::{collections::VecDeque, error::Error};
 
-use crate::{direction::Direction, vec2d::Vec2D};
+use crate::{bitgrid::BitGrid, errors::ParseInputError, parse, solutions::Solution, vec2d::Vec2D};
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Down,
-    Direction::Left,
-    Direction::Right,
-];
-
-struct Map {
+pub struct Map {
     blocks: Vec<(usize, usize)>,
     first: (usize, usize),
     last: (usize, usize),
@@ -17,24 +10,30 @@ struct Map {
 }
 
 impl Map {
-    fn from_input(input: &str, size: usize) -> Self {
+    fn from_input(input: &str, size: usize) -> Result<Self, ParseInputError> {
         let mut blocks = Vec::new();
         let first = (0, 0);
         let last = (size - 1, size - 1);
 
-        input.lines().for_each(|line| {
-            let (x, y) = line.split_once(",").unwrap();
-            let x = x.parse().unwrap();
-            let y = y.parse().unwrap();
-            blocks.push((x, y));
-        });
+        let expected = format!("a coordinate below {}", size);
+        for line in input.lines() {
+            let (x, y) = parse::split_once(input, line, ",")?;
+            let coordinate = |token| -> Result<usize, ParseInputError> {
+                let value = parse::token(input, token, &expected)?;
+                if value >= size {
+                    return Err(ParseInputError::new(&expected).at_str(input, token));
+                }
+                Ok(value)
+            };
+            blocks.push((coordinate(x)?, coordinate(y)?));
+        }
 
-        Self {
+        Ok(Self {
             blocks,
             first,
             last,
             size,
-        }
+        })
     }
 
     fn shortest_path(&self, blocks: Vec<(usize, usize)>) -> Result<i32, String> {
@@ -43,23 +42,19 @@ impl Map {
             grid.set(*y, *x, '#');
         });
 
-        let mut queue: VecDeque<((usize, usize), i32)> =
-            VecDeque::from(vec![(self.first.clone(), 0)]);
-        let mut visited = HashSet::new();
+        let mut queue: VecDeque<((usize, usize), i32)> = VecDeque::from(vec![(self.first, 0)]);
+        let mut visited = BitGrid::new(grid.size);
         while let Some(((x, y), cost)) = queue.pop_front() {
-            if visited.contains(&(x, y)) {
+            if !visited.insert(x, y) {
                 continue;
             }
-            visited.insert((x, y));
             if self.last == (x, y) {
                 return Ok(cost);
             }
 
-            for dir in DIRECTIONS {
-                if let Some((nx, ny)) = dir.checked_add((x, y)) {
-                    if grid.get(nx, ny) == Some(&'.') {
-                        queue.push_back(((nx, ny), cost + 1));
-                    }
+            for (next, c) in grid.neighbors4((x, y)) {
+                if *c == '.' {
+                    queue.push_back((next, cost + 1));
                 }
             }
         }
@@ -67,7 +62,8 @@ impl Map {
         Err("No path found".to_string())
     }
 
-    fn first_unreachable(&self) -> (usize, usize) {
+    /// The first block that cuts the exit off, if any does.
+    fn first_unreachable(&self) -> Option<(usize, usize)> {
         let mut st = 0;
         let mut ed = self.blocks.len();
 
@@ -83,67 +79,83 @@ impl Map {
             }
         }
 
-        self.blocks[ed - 1]
+        if ed == self.blocks.len() && self.shortest_path(self.blocks.clone()).is_ok() {
+            return None;
+        }
+        ed.checked_sub(1).map(|i| self.blocks[i])
     }
 }
 
-#[aoc(day18, part1)]
-pub fn part1(input: &str) -> Result<i32, String> {
-    let map = Map::from_input(input, 71);
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Parsed<'a> = Map;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Map::from_input(input, 71)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let blocks = map.blocks.get(..1024).ok_or_else(|| {
+            ParseInputError::new("at least 1024 falling bytes")
+                .with_day(18)
+                .with_found(map.blocks.len().to_string())
+        })?;
+
+        Ok(map.shortest_path(blocks.to_vec())?)
+    }
 
-    map.shortest_path(map.blocks[..1024].to_vec())
+    fn part2(map: &Self::Parsed<'_>) -> Result<String, Box<dyn Error>> {
+        let point = map.first_unreachable().ok_or("The exit is never cut off")?;
+        Ok(format!("{},{}", point.0, point.1))
+    }
 }
 
-#[aoc(day18, part2)]
-pub fn part2(input: &str) -> String {
-    let map = Map::from_input(input, 71);
+#[aoc(day18, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day18::solve_part1(input)
+}
 
-    let point = map.first_unreachable();
-    format!("{},{}", point.0, point.1)
+#[aoc(day18, part2)]
+pub fn part2(input: &str) -> Result<String, Box<dyn Error>> {
+    Day18::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const SAMPLE_INPUT_1: &str = "\
-5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
+    const SAMPLE_INPUT_1: &str = include_str!("../fixtures/2024/day18.sample1.txt");
 
     #[test]
     fn test_sample_part_1() {
-        let map = Map::from_input(SAMPLE_INPUT_1, 7);
+        let map = Map::from_input(SAMPLE_INPUT_1, 7).unwrap();
 
         assert_eq!(22, map.shortest_path(map.blocks[..12].to_vec()).unwrap());
     }
 
     #[test]
     fn test_sample_part_2() {
-        let map = Map::from_input(SAMPLE_INPUT_1, 7);
+        let map = Map::from_input(SAMPLE_INPUT_1, 7).unwrap();
+
+        assert_eq!(Some((6, 1)), map.first_unreachable());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = Map::from_input("5,4\n4;2\n", 7).err().unwrap();
+        assert_eq!(err.line, Some(2));
+
+        let err = Map::from_input("5,4\n4,7\n", 7).err().unwrap();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+
+        let err = part1(SAMPLE_INPUT_1).unwrap_err();
+        assert!(err.to_string().contains("at least 1024 falling bytes"));
 
-        assert_eq!((6, 1), map.first_unreachable());
+        let map = Map::from_input("1,1\n", 7).unwrap();
+        assert_eq!(None, map.first_unreachable());
     }
 }
@@ -1,86 +1,91 @@
-#[aoc(day19, part1)]
-pub fn part1(input: &str) -> i32 {
-    let (pattern_part, design_part) = input.split_once("\n\n").unwrap();
-    let patterns = pattern_part.split(", ").collect::<Vec<_>>();
-    let designs = design_part.lines().collect::<Vec<_>>();
+use std::error::Error;
 
-    designs
-        .iter()
-        .filter(|design| {
-            let mut possibles: Vec<bool> = vec![false; design.len() + 1];
+use crate::{errors::ParseInputError, parse, solutions::Solution};
 
-            possibles[0] = true;
+pub struct Day19;
 
-            for i in 0..design.len() {
-                if !possibles[i] {
-                    continue;
-                }
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Parsed<'a> = (Vec<&'a str>, Vec<&'a str>);
+    type Answer1 = i32;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let [pattern_part, design_part] = parse::sections_n(input, input)?;
+        let patterns = pattern_part.split(", ").collect::<Vec<_>>();
+        let designs = design_part.lines().collect::<Vec<_>>();
+        Ok((patterns, designs))
+    }
 
-                for pattern in patterns.iter() {
-                    if design[i..].starts_with(pattern) {
-                        possibles[i + pattern.len()] = true;
+    fn part1((patterns, designs): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        Ok(designs
+            .iter()
+            .filter(|design| {
+                let mut possibles: Vec<bool> = vec![false; design.len() + 1];
+
+                possibles[0] = true;
+
+                for i in 0..design.len() {
+                    if !possibles[i] {
+                        continue;
                     }
-                }
-            }
 
-            possibles[design.len()]
-        })
-        .count() as i32
-}
+                    for pattern in patterns.iter() {
+                        if design[i..].starts_with(pattern) {
+                            possibles[i + pattern.len()] = true;
+                        }
+                    }
+                }
 
-#[aoc(day19, part2)]
-pub fn part2(input: &str) -> i64 {
-    let (pattern_part, design_part) = input.split_once("\n\n").unwrap();
-    let patterns = pattern_part.split(", ").collect::<Vec<_>>();
-    let designs = design_part.lines().collect::<Vec<_>>();
+                possibles[design.len()]
+            })
+            .count() as i32)
+    }
 
-    designs
-        .iter()
-        .map(|design| {
-            let mut possibility: Vec<i64> = vec![0; design.len() + 1];
+    fn part2((patterns, designs): &Self::Parsed<'_>) -> Result<i64, Box<dyn Error>> {
+        Ok(designs
+            .iter()
+            .map(|design| {
+                let mut possibility: Vec<i64> = vec![0; design.len() + 1];
 
-            possibility[0] = 1;
+                possibility[0] = 1;
 
-            for i in 0..design.len() {
-                if possibility[i] == 0 {
-                    continue;
-                }
+                for i in 0..design.len() {
+                    if possibility[i] == 0 {
+                        continue;
+                    }
 
-                for pattern in patterns.iter() {
-                    if design[i..].starts_with(pattern) {
-                        possibility[i + pattern.len()] += possibility[i];
+                    for pattern in patterns.iter() {
+                        if design[i..].starts_with(pattern) {
+                            possibility[i + pattern.len()] += possibility[i];
+                        }
                     }
                 }
-            }
 
-            possibility[design.len()]
-        })
-        .sum()
+                possibility[design.len()]
+            })
+            .sum())
+    }
+}
+
+#[aoc(day19, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day19::solve_part1(input)
+}
+
+#[aoc(day19, part2)]
+pub fn part2(input: &str) -> Result<i64, Box<dyn Error>> {
+    Day19::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const SAMPLE_INPUT_1: &str = "\
-r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb";
-
-    #[test]
-    fn test_sample_part_1() {
-        assert_eq!(6, part1(SAMPLE_INPUT_1));
-    }
-
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(16, part2(SAMPLE_INPUT_1));
+    fn test_invalid_input() {
+        let err = Day19::parse_input("r, wr, b\nbrwrr\n").unwrap_err();
+        assert_eq!(err.day, Some(19));
+        assert_eq!(err.expected, "2 sections separated by blank lines");
     }
 }
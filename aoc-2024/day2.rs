@@ -1,15 +1,19 @@
-fn is_safe(nums: &Vec<i32>) -> bool {
+use std::error::Error;
+
+use crate::{errors::ParseInputError, parse, solutions::Solution};
+
+fn is_safe(nums: &[i32]) -> bool {
     let mut order = 0;
     for i in 1..nums.len() {
         let diff = nums[i] - nums[i - 1];
         match diff {
-            1 | 2 | 3 => {
+            1..=3 => {
                 if order == -1 {
                     return false;
                 }
                 order = 1;
             }
-            -1 | -2 | -3 => {
+            -3..=-1 => {
                 if order == 1 {
                     return false;
                 }
@@ -23,70 +27,66 @@ fn is_safe(nums: &Vec<i32>) -> bool {
     true
 }
 
-#[aoc(day2, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut safe = 0;
-    input.lines().for_each(|line| {
-        let nums = line
-            .split_whitespace()
-            .map(|x| x.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
-        if is_safe(&nums) {
-            safe += 1;
-        }
-    });
+pub struct Day2;
 
-    safe
-}
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Parsed<'a> = Vec<Vec<i32>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-#[aoc(day2, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut safe = 0;
-    input.lines().for_each(|line| {
-        let nums = line
-            .split_whitespace()
-            .map(|x| x.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>();
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        input
+            .lines()
+            .map(|line| parse::list(input, line, " ", "a level"))
+            .collect()
+    }
 
-        if is_safe(&nums) {
-            safe += 1;
-            return;
-        }
-        for i in 0..nums.len() {
-            // remove i-th element from nums
-            let mut modified = nums.clone();
-            modified.remove(i);
+    fn part1(reports: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        Ok(reports.iter().filter(|nums| is_safe(nums)).count() as i32)
+    }
 
-            if is_safe(&modified) {
+    fn part2(reports: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut safe = 0;
+        reports.iter().for_each(|nums| {
+            if is_safe(nums) {
                 safe += 1;
                 return;
             }
-        }
-    });
+            for i in 0..nums.len() {
+                // remove i-th element from nums
+                let mut modified = nums.clone();
+                modified.remove(i);
+
+                if is_safe(&modified) {
+                    safe += 1;
+                    return;
+                }
+            }
+        });
+
+        Ok(safe)
+    }
+}
+
+#[aoc(day2, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day2::solve_part1(input)
+}
 
-    safe
+#[aoc(day2, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day2::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9
-";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 2);
-    }
-
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 4);
+    fn test_invalid_input() {
+        let err = Day2::parse_input("7 6 4\n1 2 x 4\n").err().unwrap();
+        assert_eq!((err.line, err.column), (Some(2), Some(5)));
+        assert_eq!(err.found.as_deref(), Some("\"x\""));
     }
 }
@@ -1,27 +1,36 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, error::Error};
 
-use crate::{direction::Direction, vec2d::Vec2D};
+use crate::{
+    bitgrid::BitGrid, errors::ParseInputError, point::Point, solutions::Solution, vec2d::Vec2D,
+};
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::Up,
-    Direction::Down,
-    Direction::Left,
-    Direction::Right,
-];
+fn reachable(distances: &Vec2D<i32>) -> BitGrid {
+    let mut mask = BitGrid::new(distances.size);
+    for ((x, y), cost) in distances.iter_positions() {
+        if *cost != -1 {
+            mask.set(x, y);
+        }
+    }
+    mask
+}
 
-struct Map {
+pub struct Map {
     grid: Vec2D<char>,
     start: (usize, usize),
     end: (usize, usize),
 }
 
 impl Map {
-    fn from_input(input: &str) -> Self {
-        let grid = Vec2D::new_chars(input);
-        let start = grid.find_first('S').unwrap();
-        let end = grid.find_first('E').unwrap();
-
-        Self { grid, start, end }
+    fn from_input(input: &str) -> Result<Self, ParseInputError> {
+        let grid = Vec2D::parse(input, |c| matches!(c, '#' | '.' | 'S' | 'E').then_some(c))?;
+        let start = grid
+            .find_first('S')
+            .ok_or_else(|| ParseInputError::new("a start tile 'S'").with_found("none"))?;
+        let end = grid
+            .find_first('E')
+            .ok_or_else(|| ParseInputError::new("an end tile 'E'").with_found("none"))?;
+
+        Ok(Self { grid, start, end })
     }
 
     fn shortest_path(&self, start: (usize, usize)) -> Vec2D<i32> {
@@ -31,19 +40,14 @@ impl Map {
         queue.push_back((start, 0));
 
         while let Some(((x, y), cost)) = queue.pop_front() {
-            if *shortest_path.get(x, y).unwrap() != -1 {
+            if shortest_path[(x, y)] != -1 {
                 continue;
             }
 
             shortest_path.set(x, y, cost);
-            for dir in DIRECTIONS {
-                if let Some((nx, ny)) = dir.checked_add((x, y)) {
-                    match self.grid.get(nx, ny) {
-                        Some('.') | Some('E') | Some('S') => {
-                            queue.push_back(((nx, ny), cost + 1));
-                        }
-                        _ => {}
-                    }
+            for (next, c) in self.grid.neighbors4((x, y)) {
+                if let '.' | 'E' | 'S' = c {
+                    queue.push_back((next, cost + 1));
                 }
             }
         }
@@ -51,102 +55,107 @@ impl Map {
         shortest_path
     }
 
-    fn count_threshold(&self, threshold: i32, time_limit: isize, exact: bool) -> i32 {
+    fn count_threshold(
+        &self,
+        threshold: i32,
+        time_limit: i32,
+        exact: bool,
+    ) -> Result<i32, Box<dyn Error>> {
         let shortest_path_from_start = self.shortest_path(self.start);
         let shortest_path_from_end = self.shortest_path(self.end);
 
-        let shortest_path = *shortest_path_from_start
-            .get(self.end.0, self.end.1)
-            .unwrap();
+        let shortest_path = shortest_path_from_start[self.end];
+
+        // Every reachable tile as a signed point, with its distance.
+        let tiles = |distances: &Vec2D<i32>| {
+            reachable(distances)
+                .iter_ones()
+                .map(|position| Some((Point::from(position).try_cast()?, distances[position])))
+                .collect::<Option<Vec<(Point<i32>, i32)>>>()
+                .ok_or("The map is too big")
+        };
+        let reachable_from_start = tiles(&shortest_path_from_start)?;
+        let reachable_from_end = tiles(&shortest_path_from_end)?;
 
         let mut count = 0;
-        for sx in 0..self.grid.size.0 {
-            for sy in 0..self.grid.size.1 {
-                for ex in 0..self.grid.size.0 {
-                    for ey in 0..self.grid.size.1 {
-                        let len_x: isize = (sx as isize) - (ex as isize);
-                        let len_y = (sy as isize) - (ey as isize);
-                        let len = len_x.abs() + len_y.abs();
-                        if sx == ex && sy == ey {
-                            continue;
-                        }
-                        if exact && len != time_limit {
-                            continue;
-                        }
-                        if !exact && len > time_limit {
-                            continue;
-                        }
-
-                        match (
-                            shortest_path_from_start.get(sx, sy),
-                            shortest_path_from_end.get(ex, ey),
-                        ) {
-                            (Some(from_start), Some(from_end))
-                                if *from_start != -1 && *from_end != -1 =>
-                            {
-                                if shortest_path - from_start - from_end - (len as i32) >= threshold
-                                {
-                                    count += 1;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
+        for &(start, from_start) in &reachable_from_start {
+            for &(end, from_end) in &reachable_from_end {
+                let len = start.manhattan(end);
+                if len == 0 {
+                    continue;
+                }
+                if exact && len != time_limit {
+                    continue;
+                }
+                if !exact && len > time_limit {
+                    continue;
+                }
+                if shortest_path - from_start - from_end - len >= threshold {
+                    count += 1;
                 }
             }
         }
 
-        count
+        Ok(count)
     }
 }
 
-#[aoc(day20, part1)]
-pub fn part1(input: &str) -> i32 {
-    let map = Map::from_input(input);
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Parsed<'a> = Map;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Map::from_input(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        map.count_threshold(100, 2, true)
+    }
 
-    map.count_threshold(100, 2, true)
+    fn part2(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        map.count_threshold(100, 20, false)
+    }
 }
 
-#[aoc(day20, part2)]
-pub fn part2(input: &str) -> i32 {
-    let map = Map::from_input(input);
+#[aoc(day20, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day20::solve_part1(input)
+}
 
-    map.count_threshold(100, 20, false)
+#[aoc(day20, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day20::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const SAMPLE_INPUT_1: &str = "\
-###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
+    const SAMPLE_INPUT_1: &str = include_str!("../fixtures/2024/day20.sample1.txt");
 
     #[test]
     fn test_sample_part_1() {
-        let map = Map::from_input(SAMPLE_INPUT_1);
+        let map = Map::from_input(SAMPLE_INPUT_1).unwrap();
 
-        assert_eq!(1, map.count_threshold(60, 2, true));
-        assert_eq!(16, map.count_threshold(6, 2, true));
+        assert_eq!(1, map.count_threshold(60, 2, true).unwrap());
+        assert_eq!(16, map.count_threshold(6, 2, true).unwrap());
     }
 
     #[test]
     fn test_sample_part_2() {
-        let map = Map::from_input(SAMPLE_INPUT_1);
+        let map = Map::from_input(SAMPLE_INPUT_1).unwrap();
 
-        assert_eq!(285, map.count_threshold(50, 20, false));
+        assert_eq!(285, map.count_threshold(50, 20, false).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = Day20::parse_input("#####\n#S.E#\n###\n").err().unwrap();
+        assert_eq!(err.day, Some(20));
+        assert_eq!(err.line, Some(3));
     }
 }
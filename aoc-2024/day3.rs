@@ -1,62 +1,71 @@
+use std::error::Error;
+
 use regex::Regex;
 
-#[aoc(day3, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut total = 0;
-    let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
-    for captures in re.captures_iter(input) {
-        let (_, [a, b]) = captures.extract();
-        total += a.parse::<i32>().unwrap() * b.parse::<i32>().unwrap();
-    }
-    total
+use crate::{errors::ParseInputError, parse, solutions::Solution};
+
+pub enum Instruction {
+    Mul(i32, i32),
+    Do,
+    Dont,
 }
 
-#[aoc(day3, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut total = 0;
-    let instruction_re = Regex::new(r"(mul\([0-9]{1,3},[0-9]{1,3}\)|do\(\)|don\'t\(\))").unwrap();
-    let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
-    let do_re = Regex::new(r"do\(\)").unwrap();
-    let mut enabled = true;
-    for captures in instruction_re.captures_iter(input) {
-        let (_, [instruction]) = captures.extract();
-        match re.captures(instruction) {
-            Some(captures) => {
-                let (_, [a, b]) = captures.extract();
-                if enabled {
-                    total += a.parse::<i32>().unwrap() * b.parse::<i32>().unwrap();
-                }
-            }
-            None => {
-                if do_re.is_match(instruction) {
-                    enabled = true;
-                } else {
-                    enabled = false;
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Parsed<'a> = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\)").unwrap();
+        re.captures_iter(input)
+            .map(|captures| match (captures.get(1), captures.get(2)) {
+                (Some(a), Some(b)) => Ok(Instruction::Mul(
+                    parse::token(input, a.as_str(), "a number")?,
+                    parse::token(input, b.as_str(), "a number")?,
+                )),
+                _ if &captures[0] == "do()" => Ok(Instruction::Do),
+                _ => Ok(Instruction::Dont),
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        Ok(instructions
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum())
+    }
+
+    fn part2(instructions: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total = 0;
+        let mut enabled = true;
+        for instruction in instructions {
+            match instruction {
+                Instruction::Mul(a, b) => {
+                    if enabled {
+                        total += a * b;
+                    }
                 }
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
             }
         }
+        Ok(total)
     }
-    total
 }
 
-#[cfg(test)]
-mod tests {
-    const SAMPLE_INPUT: &str = "\
-xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
-";
-    const SAMPLE_INPUT2: &str = "\
-xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
-";
-
-    use super::*;
-
-    #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 161);
-    }
+#[aoc(day3, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day3::solve_part1(input)
+}
 
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT2), 48);
-    }
+#[aoc(day3, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day3::solve_part2(input)
 }
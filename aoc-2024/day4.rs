@@ -1,179 +1,88 @@
+use std::error::Error;
+
+use crate::{
+    direction::Direction,
+    errors::ParseInputError,
+    solutions::Solution,
+    vec2d::{Rect, Vec2D},
+};
+
 const XMAS: &str = "XMAS";
 
-#[aoc(day4, part1)]
-pub fn part1(input: &str) -> i32 {
-    let dir: Vec<(i32, i32)> = vec![
-        (0, 1),
-        (1, 0),
-        (1, 1),
-        (1, -1),
-        (-1, 0),
-        (0, -1),
-        (-1, -1),
-        (-1, 1),
-    ];
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Parsed<'a> = Vec2D<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Ok(Vec2D::parse(input, Some)?)
+    }
 
-    let board = input
-        .lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
-    let mut count = 0;
-    for i in 0..board.len() {
-        for j in 0..board[i].len() {
-            for (x, y) in dir.iter() {
-                let mut found = true;
-                for word_index in 0..XMAS.len() {
-                    let tx = i as i32 - x * (word_index as i32);
-                    if tx < 0 {
-                        found = false;
-                        break;
-                    }
-                    match board.get(tx as usize) {
-                        Some(row) => {
-                            let ty = j as i32 - y * (word_index as i32);
-                            if ty < 0 {
-                                found = false;
-                                break;
-                            }
-                            match row.get(ty as usize) {
-                                Some(c) => {
-                                    if *c != XMAS.chars().nth(word_index).unwrap() {
-                                        found = false;
-                                        break;
-                                    }
-                                }
-                                None => {
-                                    found = false;
-                                    break;
-                                }
-                            }
-                        }
-                        None => {
-                            found = false;
-                            break;
-                        }
-                    }
+    fn part1(board: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        Ok(board
+            .iter_positions()
+            .map(|(pos, _)| {
+                Direction::ALL
+                    .iter()
+                    .filter(|dir| {
+                        board
+                            .walk(pos, **dir)
+                            .map(|(_, c)| *c)
+                            .take(XMAS.len())
+                            .eq(XMAS.chars())
+                    })
+                    .count() as i32
+            })
+            .sum())
+    }
+
+    fn part2(board: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut count = 0;
+
+        for i in 0..board.size.0.saturating_sub(2) {
+            for j in 0..board.size.1.saturating_sub(2) {
+                let window = board.view(Rect::new((i, j), (3, 3)));
+                if window.get(1, 1) != Some(&'A') {
+                    continue;
                 }
-                if found {
+
+                let is_mas = |a: (usize, usize), b: (usize, usize)| {
+                    matches!(
+                        (window.get(a.0, a.1), window.get(b.0, b.1)),
+                        (Some('M'), Some('S')) | (Some('S'), Some('M'))
+                    )
+                };
+                if is_mas((0, 0), (2, 2)) && is_mas((0, 2), (2, 0)) {
                     count += 1;
                 }
             }
         }
+
+        Ok(count)
     }
-    count
+}
+
+#[aoc(day4, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day4::solve_part1(input)
 }
 
 #[aoc(day4, part2)]
-pub fn part2(input: &str) -> i32 {
-    let board = input
-        .lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
-    let mut count = 0;
-    let dir: Vec<(i32, i32)> = vec![(-1, -1), (-1, 1), (1, 1), (1, -1)];
-    for i in 0..board.len() {
-        for j in 0..board[i].len() {
-            if board[i][j] == 'A' {
-                for idx in 0..dir.len() {
-                    let mut found = true;
-                    for k in 0..2 {
-                        let (x, y) = dir[(idx + k as usize) % dir.len()];
-                        if (i as i32) + x >= 0 {
-                            match board.get(((i as i32) + x) as usize) {
-                                Some(row) => {
-                                    if (j as i32) + y >= 0 {
-                                        match row.get(((j as i32) + y) as usize) {
-                                            Some('M') => {}
-                                            _ => {
-                                                found = false;
-                                                break;
-                                            }
-                                        }
-                                    } else {
-                                        found = false;
-                                        break;
-                                    }
-                                }
-                                _ => {
-                                    found = false;
-                                    break;
-                                }
-                            }
-                        } else {
-                            found = false;
-                            break;
-                        }
-                    }
-                    if !found {
-                        continue;
-                    }
-                    for k in 2..4 {
-                        let (x, y) = dir[(idx + k as usize) % dir.len()];
-                        if (i as i32) + x >= 0 {
-                            match board.get(((i as i32) + x) as usize) {
-                                Some(row) => {
-                                    if (j as i32) + y >= 0 {
-                                        match row.get(((j as i32) + y) as usize) {
-                                            Some(c) => {
-                                                if *c != 'S' {
-                                                    found = false;
-                                                    break;
-                                                }
-                                            }
-                                            None => {
-                                                found = false;
-                                                break;
-                                            }
-                                        }
-                                    } else {
-                                        found = false;
-                                        break;
-                                    }
-                                }
-                                None => {
-                                    found = false;
-                                    break;
-                                }
-                            }
-                        } else {
-                            found = false;
-                            break;
-                        }
-                    }
-                    if found {
-                        count += 1;
-                    }
-                }
-            }
-        }
-    }
-    count
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day4::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX
-";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 18);
-    }
-
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 9);
+    fn test_invalid_input() {
+        let err = Day4::parse_input("XMAS\nXMA\n").unwrap_err();
+        assert_eq!(err.day, Some(4));
+        assert_eq!(err.line, Some(2));
     }
 }
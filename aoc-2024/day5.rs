@@ -1,134 +1,109 @@
-use std::collections::{hash_map, hash_set};
+use std::{
+    collections::{hash_map, hash_set},
+    error::Error,
+};
 
-use itertools::Itertools;
+use crate::{errors::ParseInputError, parse, solutions::Solution};
 
-#[aoc(day5, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut total = 0;
-    let (edges, books) = input.split_once("\n\n").unwrap();
-
-    let mut before_map: hash_map::HashMap<&str, Vec<&str>> = hash_map::HashMap::new();
-    edges.lines().for_each(|l| {
-        let (before, after) = l.split_once("|").unwrap();
-        if !before_map.contains_key(&before) {
-            before_map.insert(before, vec![]);
-        }
-        before_map.get_mut(&before).unwrap().push(after);
-    });
-
-    books.lines().for_each(|l| {
-        let order = l.split(",").collect_vec();
-        let mut visited = hash_set::HashSet::new();
-
-        let mut success = true;
-        for x in order.clone() {
-            if let Some(before) = before_map.get(&x) {
-                if before.iter().any(|&y| visited.contains(y)) {
-                    success = false;
-                    break;
-                }
-            }
-            visited.insert(x);
-        }
+type Rules = hash_map::HashMap<i32, Vec<i32>>;
 
-        if success {
-            total += order.get(order.len() / 2).unwrap().parse::<i32>().unwrap();
+fn middle_page(order: &[i32]) -> i32 {
+    order[order.len() / 2]
+}
+
+fn is_ordered(before_map: &Rules, order: &[i32]) -> bool {
+    let mut visited = hash_set::HashSet::new();
+
+    for &x in order {
+        if let Some(before) = before_map.get(&x) {
+            if before.iter().any(|y| visited.contains(y)) {
+                return false;
+            }
         }
-    });
+        visited.insert(x);
+    }
 
-    total
+    true
 }
 
-#[aoc(day5, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut total = 0;
-    let (edges, books) = input.split_once("\n\n").unwrap();
-
-    let mut before_map: hash_map::HashMap<&str, Vec<&str>> = hash_map::HashMap::new();
-    edges.lines().for_each(|l| {
-        let (before, after) = l.split_once("|").unwrap();
-        if !before_map.contains_key(&before) {
-            before_map.insert(before, vec![]);
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Parsed<'a> = (Rules, Vec<Vec<i32>>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let [edges, books] = parse::sections_n(input, input)?;
+
+        let mut before_map: Rules = hash_map::HashMap::new();
+        for l in edges.lines() {
+            let (before, after) = parse::pair(input, l, "|", "a page number")?;
+            before_map.entry(before).or_default().push(after);
         }
-        before_map.get_mut(&before).unwrap().push(after);
-    });
-
-    books.lines().for_each(|l| {
-        let order = l.split(",").collect_vec();
-        let mut visited = hash_set::HashSet::new();
-
-        let mut success = true;
-        for x in order.clone() {
-            if let Some(before) = before_map.get(&x) {
-                // println!("{:?} {:?}", x, before);
-                if before.iter().any(|&y| visited.contains(y)) {
-                    success = false;
-                    break;
-                }
+
+        let books = books
+            .lines()
+            .map(|l| parse::list(input, l, ",", "a page number"))
+            .collect::<Result<_, _>>()?;
+
+        Ok((before_map, books))
+    }
+
+    fn part1((before_map, books): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total = 0;
+
+        for order in books {
+            if is_ordered(before_map, order) {
+                total += middle_page(order);
             }
-            visited.insert(x);
         }
 
-        if !success {
-            let mut new_order = order.clone();
-            new_order.sort_by(|a, b| {
-                (before_map.contains_key(b) && before_map[b].contains(a)).cmp(&true)
-            });
-            total += new_order
-                .get(new_order.len() / 2)
-                .unwrap()
-                .parse::<i32>()
-                .unwrap();
-            // println!("total: {}", total);
+        Ok(total)
+    }
+
+    fn part2((before_map, books): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut total = 0;
+
+        for order in books {
+            if !is_ordered(before_map, order) {
+                let mut new_order = order.clone();
+                new_order.sort_by(|a, b| {
+                    (before_map.contains_key(b) && before_map[b].contains(a)).cmp(&true)
+                });
+                total += middle_page(&new_order);
+            }
         }
-        // println!("---");
-    });
 
-    total
+        Ok(total)
+    }
+}
+
+#[aoc(day5, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day5::solve_part1(input)
+}
+
+#[aoc(day5, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day5::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47
-";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 143);
-    }
+    fn test_invalid_input() {
+        let err = Day5::parse_input("47|53\n").unwrap_err();
+        assert_eq!(err.day, Some(5));
+        assert_eq!(err.expected, "2 sections separated by blank lines");
 
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 123);
+        let err = Day5::parse_input("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+
+        let err = Day5::parse_input("47|53\n\n53,x,47\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(4)));
     }
 }
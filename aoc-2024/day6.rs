@@ -1,90 +1,24 @@
-use std::collections::HashSet;
+use std::error::Error;
 
+use crate::bitgrid::{BitGrid, BitGrid4};
 use crate::direction::Direction;
+use crate::errors::ParseInputError;
+use crate::solutions::Solution;
 use crate::vec2d::Vec2D;
 
-#[aoc(day6, part1)]
-pub fn part1(input: &str) -> i32 {
-    let map = Vec2D::new_chars(input);
-    let mut visited: HashSet<(usize, usize, Direction)> = HashSet::new();
-    let mut visited_pos: HashSet<(usize, usize)> = HashSet::new();
+fn is_loop(map: &Vec2D<char>, start: (usize, usize), visited: &mut BitGrid4) -> bool {
+    visited.clear();
 
-    let mut pos: (usize, usize) = (0, 0);
+    let mut pos: (usize, usize) = start;
     let mut dir = Direction::Up;
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '^' {
-                    pos = (i, j);
-                }
-            }
-        }
-    }
 
     while pos.0 < map.size.0 && pos.1 < map.size.1 {
-        if visited.contains(&(pos.0, pos.1, dir)) {
-            break;
-        }
-        visited.insert((pos.0, pos.1, dir));
-        visited_pos.insert((pos.0, pos.1));
-        match dir {
-            Direction::Up => {
-                if let Some(c) = map.get(pos.0 - 1, pos.1) {
-                    if *c != '#' {
-                        pos.0 -= 1;
-                    } else {
-                        dir = Direction::Right;
-                    }
-                }
-            }
-            Direction::Down => {
-                if let Some(c) = map.get(pos.0 + 1, pos.1) {
-                    if *c != '#' {
-                        pos.0 += 1;
-                    } else {
-                        dir = Direction::Left;
-                    }
-                }
-            }
-            Direction::Left => {
-                if let Some(c) = map.get(pos.0, pos.1 - 1) {
-                    if *c != '#' {
-                        pos.1 -= 1;
-                    } else {
-                        dir = Direction::Up;
-                    }
-                }
-            }
-            Direction::Right => {
-                if let Some(c) = map.get(pos.0, pos.1 + 1) {
-                    if *c != '#' {
-                        pos.1 += 1;
-                    } else {
-                        dir = Direction::Down;
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-
-    visited_pos.len() as i32
-}
-
-fn is_loop(map: &Vec2D<char>, start: (usize, usize)) -> bool {
-    let mut visited: HashSet<(usize, usize, Direction)> = HashSet::new();
-
-    let mut pos: (usize, usize) = start.clone();
-    let mut dir = Direction::Up;
-
-    while pos.0 < map.size.0 && pos.1 < map.size.1 {
-        if visited.contains(&(pos.0, pos.1, dir)) {
+        if !visited.insert(pos.0, pos.1, dir) {
             return true;
         }
-        visited.insert((pos.0, pos.1, dir));
         match dir {
             Direction::Up => {
-                if pos.0 <= 0 {
+                if pos.0 == 0 {
                     return false;
                 }
                 if let Some(c) = map.get(pos.0 - 1, pos.1) {
@@ -109,7 +43,7 @@ fn is_loop(map: &Vec2D<char>, start: (usize, usize)) -> bool {
                 }
             }
             Direction::Left => {
-                if pos.1 <= 0 {
+                if pos.1 == 0 {
                     return false;
                 }
                 if let Some(c) = map.get(pos.0, pos.1 - 1) {
@@ -140,65 +74,130 @@ fn is_loop(map: &Vec2D<char>, start: (usize, usize)) -> bool {
     false
 }
 
-#[aoc(day6, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut map = Vec2D::new_chars(input);
-
-    let mut start: (usize, usize) = (0, 0);
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '^' {
-                    start = (i, j);
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Parsed<'a> = (Vec2D<char>, (usize, usize));
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let map = Vec2D::parse(input, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
+        let start = map
+            .find_first('^')
+            .ok_or_else(|| ParseInputError::new("a guard '^'").with_found("none"))?;
+        Ok((map, start))
+    }
+
+    fn part1(&(ref map, mut pos): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut visited = BitGrid4::new(map.size);
+        let mut visited_pos = BitGrid::new(map.size);
+
+        let mut dir = Direction::Up;
+
+        while pos.0 < map.size.0 && pos.1 < map.size.1 {
+            if !visited.insert(pos.0, pos.1, dir) {
+                break;
+            }
+            visited_pos.set(pos.0, pos.1);
+            match dir {
+                Direction::Up => {
+                    if let Some(c) = pos.0.checked_sub(1).and_then(|i| map.get(i, pos.1)) {
+                        if *c != '#' {
+                            pos.0 -= 1;
+                        } else {
+                            dir = Direction::Right;
+                        }
+                    }
+                }
+                Direction::Down => {
+                    if let Some(c) = map.get(pos.0 + 1, pos.1) {
+                        if *c != '#' {
+                            pos.0 += 1;
+                        } else {
+                            dir = Direction::Left;
+                        }
+                    }
+                }
+                Direction::Left => {
+                    if let Some(c) = pos.1.checked_sub(1).and_then(|j| map.get(pos.0, j)) {
+                        if *c != '#' {
+                            pos.1 -= 1;
+                        } else {
+                            dir = Direction::Up;
+                        }
+                    }
                 }
+                Direction::Right => {
+                    if let Some(c) = map.get(pos.0, pos.1 + 1) {
+                        if *c != '#' {
+                            pos.1 += 1;
+                        } else {
+                            dir = Direction::Down;
+                        }
+                    }
+                }
+                _ => {}
             }
         }
+
+        Ok(visited_pos.count_ones() as i32)
     }
 
-    let mut count = 0;
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            let c = map.get(i, j).unwrap().clone();
-            if c == '#' || c == '^' {
-                continue;
-            }
-            map.set(i, j, '#');
+    fn part2((map, start): &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let mut map = map.clone();
+        let mut visited = BitGrid4::new(map.size);
+        let mut count = 0;
+        for i in 0..map.size.0 {
+            for j in 0..map.size.1 {
+                let c = map[(i, j)];
+                if c == '#' || c == '^' {
+                    continue;
+                }
+                map.set(i, j, '#');
 
-            if is_loop(&map, start) {
-                count += 1;
-            }
+                if is_loop(&map, *start, &mut visited) {
+                    count += 1;
+                }
 
-            map.set(i, j, c);
+                map.set(i, j, c);
+            }
         }
+
+        Ok(count)
     }
+}
 
-    count
+#[aoc(day6, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day6::solve_part1(input)
+}
+
+#[aoc(day6, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day6::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...
-";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 41);
+    fn test_leaving_by_the_top_or_left() {
+        assert_eq!(part1(".^.\n...\n").unwrap(), 1);
+        assert_eq!(part1("#...\n...#\n^.#.\n").unwrap(), 4);
     }
 
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 6);
+    fn test_invalid_input() {
+        let err = Day6::parse_input("....\n.#..\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input for day 6: expected a guard '^', found none"
+        );
+
+        let err = Day6::parse_input("..^.\n.x..\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
     }
 }
@@ -1,143 +1,85 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error};
 
-use crate::vec2d::Vec2D;
+use crate::{errors::ParseInputError, point::Point, solutions::Solution, vec2d::Vec2D};
 
-#[aoc(day8, part1)]
-pub fn part1(input: &str) -> i32 {
-    let mut total = 0;
-    let mut map = Vec2D::new_chars(input);
-    let mut freq: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+/// Antenna positions by frequency.
+type Antennas = HashMap<char, Vec<Point<i64>>>;
 
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if c.is_alphanumeric() {
-                    if !freq.contains_key(c) {
-                        freq.insert(*c, vec![]);
-                    }
-                    freq.get_mut(c).unwrap().push((i, j));
-                }
-            }
+/// Finds every antenna on `map`.
+fn antennas(map: &Vec2D<char>) -> Result<Antennas, Box<dyn Error>> {
+    let mut antennas = Antennas::new();
+    for (position, c) in map.iter_positions() {
+        if c.is_alphanumeric() {
+            let position = Point::from(position)
+                .try_cast()
+                .ok_or("The map is too big")?;
+            antennas.entry(*c).or_default().push(position);
         }
     }
+    Ok(antennas)
+}
 
-    for (_, v) in freq.iter() {
-        for i in 0..v.len() {
-            for j in 0..v.len() {
-                if i == j {
-                    continue;
-                }
-                let (x0, y0) = v[i];
-                let (x1, y1) = v[j];
-                let antenna = (
-                    x0 as i32 - x1 as i32 + x0 as i32,
-                    y0 as i32 - y1 as i32 + y0 as i32,
-                );
-                if antenna.0 >= 0
-                    && antenna.1 >= 0
-                    && antenna.0 < map.size.0 as i32
-                    && antenna.1 < map.size.1 as i32
-                {
-                    map.set(antenna.0 as usize, antenna.1 as usize, '#');
-                }
-            }
-        }
-    }
+pub struct Day8;
 
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '#' {
-                    total += 1;
-                }
-            }
-        }
-    }
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Parsed<'a> = Vec2D<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    total
-}
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Ok(Vec2D::parse(input, Some)?)
+    }
 
-#[aoc(day8, part2)]
-pub fn part2(input: &str) -> i32 {
-    let mut total = 0;
-    let mut map = Vec2D::new_chars(input);
-    let mut freq: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+    fn part1(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let freq = antennas(map)?;
+        let mut map = map.clone();
 
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if c.is_alphanumeric() {
-                    if !freq.contains_key(c) {
-                        freq.insert(*c, vec![]);
+        for v in freq.values() {
+            for i in 0..v.len() {
+                for j in 0..v.len() {
+                    if i == j {
+                        continue;
                     }
-                    freq.get_mut(c).unwrap().push((i, j));
+                    let antenna = v[i] * 2 - v[j];
+                    map.set_point(antenna, '#');
                 }
             }
         }
-    }
 
-    for (_, v) in freq.iter() {
-        for i in 0..v.len() {
-            for j in 0..v.len() {
-                if i == j {
-                    continue;
-                }
-                let (x0, y0) = v[i];
-                let (x1, y1) = v[j];
-                let step0 = x0 as i32 - x1 as i32;
-                let step1 = y0 as i32 - y1 as i32;
-                let mut antenna = (x0 as i32, y0 as i32);
-                while antenna.0 >= 0
-                    && antenna.1 >= 0
-                    && antenna.0 < map.size.0 as i32
-                    && antenna.1 < map.size.1 as i32
-                {
-                    map.set(antenna.0 as usize, antenna.1 as usize, '#');
-                    antenna = (antenna.0 + step0, antenna.1 + step1);
-                }
-            }
-        }
+        Ok(map.find_all('#').len() as i32)
     }
 
-    for i in 0..map.size.0 {
-        for j in 0..map.size.1 {
-            if let Some(c) = map.get(i, j) {
-                if *c == '#' {
-                    total += 1;
+    fn part2(map: &Self::Parsed<'_>) -> Result<i32, Box<dyn Error>> {
+        let freq = antennas(map)?;
+        let mut map = map.clone();
+
+        for v in freq.values() {
+            for i in 0..v.len() {
+                for j in 0..v.len() {
+                    if i == j {
+                        continue;
+                    }
+                    let step = v[i] - v[j];
+                    let mut antenna = v[i];
+                    while map.get_point(antenna).is_some() {
+                        map.set_point(antenna, '#');
+                        antenna += step;
+                    }
                 }
             }
         }
-    }
 
-    total
+        Ok(map.find_all('#').len() as i32)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    const SAMPLE_INPUT: &str = "\
-............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-";
-
-    use super::*;
-
-    #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 14);
-    }
+#[aoc(day8, part1)]
+pub fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day8::solve_part1(input)
+}
 
-    #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 34);
-    }
+#[aoc(day8, part2)]
+pub fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    Day8::solve_part2(input)
 }
@@ -1,97 +1,135 @@
-use std::cmp::min;
+use std::{cmp::min, error::Error};
+
+use crate::{errors::ParseInputError, solutions::Solution};
 
 fn sum_range(start: u64, size: u64) -> u64 {
+    if size == 0 {
+        return 0;
+    }
     if start == 0 {
         return (size - 1) * size / 2;
     }
-    return (start + size - 1) * (start + size) / 2 - start * (start - 1) / 2;
+    (start + size - 1) * (start + size) / 2 - start * (start - 1) / 2
 }
 
-#[aoc(day9, part1)]
-pub fn part1(input: &str) -> u64 {
-    let mut total: u64 = 0;
-    let mut nums = input
-        .chars()
-        .map(|c| c.to_string().parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-    let mut digit = 0;
-    let mut last_id = nums.len() / 2;
-
-    let mut i: usize = 0;
-    while i <= last_id * 2 {
-        if i % 2 == 0 {
-            let id = i as u64 / 2;
-            let sum = sum_range(digit, nums[i]);
-            total += id * sum;
-            digit += nums[i];
-
-            // println!("- {} {}", id, nums[i]);
-        } else {
-            while i <= last_id * 2 && nums[i] > 0 {
-                let filling = min(nums[last_id * 2], nums[i]);
-                let sum = sum_range(digit, filling);
-                total += last_id as u64 * sum;
-                digit += filling;
-                nums[i] -= filling;
-                nums[last_id * 2] -= filling;
-
-                // println!("+ {} {}", last_id, filling);
-                while i <= last_id * 2 && nums[last_id * 2] <= 0 {
-                    last_id -= 1;
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Parsed<'a> = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        let disk_map = input.trim_end();
+        if disk_map.is_empty() {
+            return Err(ParseInputError::new("a disk map").with_found("end of input"));
+        }
+        disk_map
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(u64::from)
+                    .ok_or_else(|| ParseInputError::new("a digit").at(input, i, c.len_utf8()))
+            })
+            .collect()
+    }
+
+    fn part1(nums: &Self::Parsed<'_>) -> Result<u64, Box<dyn Error>> {
+        let mut nums = nums.clone();
+        let mut total: u64 = 0;
+        let mut digit = 0;
+        // A disk map can end with free space, so the last file isn't always
+        // the last number.
+        let mut last_id = (nums.len() - 1) / 2;
+
+        let mut i: usize = 0;
+        while i <= last_id * 2 {
+            if i.is_multiple_of(2) {
+                let id = i as u64 / 2;
+                let sum = sum_range(digit, nums[i]);
+                total += id * sum;
+                digit += nums[i];
+
+                // println!("- {} {}", id, nums[i]);
+            } else {
+                while i <= last_id * 2 && nums[i] > 0 {
+                    let filling = min(nums[last_id * 2], nums[i]);
+                    let sum = sum_range(digit, filling);
+                    total += last_id as u64 * sum;
+                    digit += filling;
+                    nums[i] -= filling;
+                    nums[last_id * 2] -= filling;
+
+                    // println!("+ {} {}", last_id, filling);
+                    while i <= last_id * 2 && nums[last_id * 2] == 0 {
+                        last_id -= 1;
+                    }
                 }
             }
-        }
 
-        // println!("total = {}", total);
-        i += 1;
+            // println!("total = {}", total);
+            i += 1;
+        }
+        Ok(total)
     }
-    total
-}
 
-#[aoc(day9, part2)]
-pub fn part2(input: &str) -> u64 {
-    let mut total: u64 = 0;
-    let nums = input
-        .chars()
-        .map(|c| c.to_string().parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-    let mut filled = vec![0; nums.len()];
-
-    'outer: for i in (0..nums.len()).rev() {
-        if i % 2 == 0 {
-            let mut pos = 0;
-            let id = i as u64 / 2;
-            for j in 0..i {
-                if j % 2 == 1 {
-                    if nums[i] + filled[j] <= nums[j] {
+    fn part2(nums: &Self::Parsed<'_>) -> Result<u64, Box<dyn Error>> {
+        let mut total: u64 = 0;
+        let mut filled = vec![0; nums.len()];
+
+        'outer: for i in (0..nums.len()).rev() {
+            if i.is_multiple_of(2) {
+                let mut pos = 0;
+                let id = i as u64 / 2;
+                for j in 0..i {
+                    if !j.is_multiple_of(2) && nums[i] + filled[j] <= nums[j] {
                         total += id * sum_range(pos + filled[j], nums[i]);
                         filled[j] += nums[i];
                         continue 'outer;
                     }
+                    pos += nums[j];
                 }
-                pos += nums[j];
+                total += id * sum_range(pos, nums[i]);
             }
-            total += id * sum_range(pos, nums[i]);
         }
+
+        Ok(total)
     }
+}
 
-    total
+#[aoc(day9, part1)]
+pub fn part1(input: &str) -> Result<u64, Box<dyn Error>> {
+    Day9::solve_part1(input)
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &str) -> Result<u64, Box<dyn Error>> {
+    Day9::solve_part2(input)
 }
 
 #[cfg(test)]
 mod tests {
-    const SAMPLE_INPUT: &str = "\
-2333133121414131402";
-
     use super::*;
 
     #[test]
-    fn test_sample_part_1() {
-        assert_eq!(part1(SAMPLE_INPUT), 1928);
+    fn test_invalid_input() {
+        assert_eq!(part1("2333133121414131402\n").unwrap(), 1928);
+
+        let err = Day9::parse_input("23331x3121").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(6)));
+
+        for input in ["", "\n"] {
+            let err = Day9::parse_input(input).unwrap_err();
+            assert_eq!(err.expected, "a disk map");
+        }
     }
 
     #[test]
-    fn test_sample_part_2() {
-        assert_eq!(part2(SAMPLE_INPUT), 2858);
+    fn test_short_disk_maps() {
+        assert_eq!(part1("0").unwrap(), 0);
+        assert_eq!(part1("12").unwrap(), 0);
+        assert_eq!(part1("1012").unwrap(), 1);
+        assert_eq!(part2("1012").unwrap(), 1);
     }
 }
@@ -1,3 +1,11 @@
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr},
+    str::FromStr,
+};
+
+use crate::errors::ParseDirectionError;
+
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 pub enum Direction {
     Up,
@@ -11,6 +19,50 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// The cardinal directions followed by the diagonal ones.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// Position of the direction in [`Direction::ALL`].
+    pub fn index(self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+            Direction::UpLeft => 4,
+            Direction::UpRight => 5,
+            Direction::DownLeft => 6,
+            Direction::DownRight => 7,
+        }
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        self.index() < 4
+    }
+
     pub fn checked_add(self, idx: (usize, usize)) -> Option<(usize, usize)> {
         let pair: (isize, isize) = self.into();
         match (
@@ -47,6 +99,185 @@ impl Direction {
             Direction::DownRight => Direction::DownLeft,
         }
     }
+
+    pub fn counter_clockwise(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+            Direction::Right => Direction::Up,
+            Direction::UpLeft => Direction::DownLeft,
+            Direction::UpRight => Direction::UpLeft,
+            Direction::DownLeft => Direction::DownRight,
+            Direction::DownRight => Direction::UpRight,
+        }
+    }
+
+    /// Turns `n` quarter-turns clockwise, or counter-clockwise if negative.
+    pub fn turn(self, n: i32) -> Self {
+        match n.rem_euclid(4) {
+            0 => self,
+            1 => self.clockwise(),
+            2 => self.opposite(),
+            3 => self.counter_clockwise(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the fewest quarter-turns from `a` to `b` in either direction,
+    /// or `None` if `b` can't be reached by quarter-turns (e.g. `Up` to
+    /// `UpRight`).
+    pub fn turns_between(a: Self, b: Self) -> Option<u32> {
+        if a == b {
+            Some(0)
+        } else if a.clockwise() == b || a.counter_clockwise() == b {
+            Some(1)
+        } else if a.opposite() == b {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    /// Accepts arrows (`^v<>`), `UDLR` and compass points (`NSEW`), in either
+    /// case.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            'V' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            _ => Err(ParseDirectionError(value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+            Direction::UpLeft => '↖',
+            Direction::UpRight => '↗',
+            Direction::DownLeft => '↙',
+            Direction::DownRight => '↘',
+        };
+        write!(f, "{}", arrow)
+    }
+}
+
+/// A set of directions stored as one bit per direction.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    pub const EMPTY: DirectionSet = DirectionSet(0);
+    pub const CARDINAL: DirectionSet = DirectionSet(0b0000_1111);
+    pub const DIAGONAL: DirectionSet = DirectionSet(0b1111_0000);
+    pub const ALL: DirectionSet = DirectionSet(0b1111_1111);
+
+    pub fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Adds the direction and returns whether it was newly added.
+    pub fn insert(&mut self, direction: Direction) -> bool {
+        let bit = 1 << direction.index();
+        let added = self.0 & bit == 0;
+        self.0 |= bit;
+        added
+    }
+
+    /// Removes the direction and returns whether it was present.
+    pub fn remove(&mut self, direction: Direction) -> bool {
+        let bit = 1 << direction.index();
+        let present = self.0 & bit != 0;
+        self.0 &= !bit;
+        present
+    }
+
+    pub fn contains(self, direction: Direction) -> bool {
+        self.0 & (1 << direction.index()) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Yields the directions in [`Direction::ALL`] order.
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |direction| self.contains(*direction))
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for direction in iter {
+            set.insert(direction);
+        }
+        set
+    }
+}
+
+impl<const N: usize> From<[Direction; N]> for DirectionSet {
+    fn from(directions: [Direction; N]) -> Self {
+        directions.into_iter().collect()
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.union(other)
+    }
+}
+
+impl BitAnd for DirectionSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.intersection(other)
+    }
+}
+
+impl std::fmt::Debug for DirectionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
 }
 
 impl From<Direction> for (isize, isize) {
@@ -63,3 +294,307 @@ impl From<Direction> for (isize, isize) {
         }
     }
 }
+
+/// A step between neighbouring cells of a hex grid in axial `(q, r)`
+/// coordinates, with pointy-topped hexes.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum HexDirection {
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl HexDirection {
+    /// All six directions, clockwise from `East`.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    pub fn checked_add(self, idx: (usize, usize)) -> Option<(usize, usize)> {
+        let pair: (isize, isize) = self.into();
+        match (
+            idx.0.checked_add_signed(pair.0),
+            idx.1.checked_add_signed(pair.1),
+        ) {
+            (Some(q), Some(r)) => Some((q, r)),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(3)
+    }
+
+    /// Turns 60° clockwise.
+    pub fn clockwise(self) -> Self {
+        self.turn(1)
+    }
+
+    /// Turns 60° counter-clockwise.
+    pub fn counter_clockwise(self) -> Self {
+        self.turn(-1)
+    }
+
+    /// Turns `n` sixth-turns clockwise, or counter-clockwise if negative.
+    pub fn turn(self, n: i32) -> Self {
+        let index = HexDirection::ALL.iter().position(|d| *d == self).unwrap();
+        HexDirection::ALL[(index as i32 + n).rem_euclid(6) as usize]
+    }
+
+    /// Number of steps between two axial positions.
+    pub fn distance(a: (isize, isize), b: (isize, isize)) -> usize {
+        let dq = a.0 - b.0;
+        let dr = a.1 - b.1;
+        ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as usize
+    }
+}
+
+impl From<HexDirection> for (isize, isize) {
+    fn from(value: HexDirection) -> Self {
+        match value {
+            HexDirection::East => (1, 0),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+        }
+    }
+}
+
+/// A step to one of the six face neighbours of a cell in a 3D grid.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub enum Direction3D {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl Direction3D {
+    pub const ALL: [Direction3D; 6] = [
+        Direction3D::PosX,
+        Direction3D::NegX,
+        Direction3D::PosY,
+        Direction3D::NegY,
+        Direction3D::PosZ,
+        Direction3D::NegZ,
+    ];
+
+    /// Offsets to all 26 cells sharing a face, edge or corner with a cell.
+    pub fn offsets26() -> impl Iterator<Item = (isize, isize, isize)> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| (x, y, z))))
+            .filter(|offset| *offset != (0, 0, 0))
+    }
+
+    pub fn checked_add(self, idx: (usize, usize, usize)) -> Option<(usize, usize, usize)> {
+        checked_add_3d(idx, self.into())
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction3D::PosX => Direction3D::NegX,
+            Direction3D::NegX => Direction3D::PosX,
+            Direction3D::PosY => Direction3D::NegY,
+            Direction3D::NegY => Direction3D::PosY,
+            Direction3D::PosZ => Direction3D::NegZ,
+            Direction3D::NegZ => Direction3D::PosZ,
+        }
+    }
+
+    /// Rotates 90° around `axis` by the right-hand rule. Directions parallel
+    /// to the axis are unchanged.
+    pub fn rotate(self, axis: Direction3D) -> Self {
+        let (ax, ay, az): (isize, isize, isize) = axis.into();
+        let (vx, vy, vz): (isize, isize, isize) = self.into();
+        let cross = (ay * vz - az * vy, az * vx - ax * vz, ax * vy - ay * vx);
+        if cross == (0, 0, 0) {
+            return self;
+        }
+        Direction3D::try_from(cross).unwrap()
+    }
+}
+
+pub(crate) fn checked_add_3d(
+    idx: (usize, usize, usize),
+    offset: (isize, isize, isize),
+) -> Option<(usize, usize, usize)> {
+    Some((
+        idx.0.checked_add_signed(offset.0)?,
+        idx.1.checked_add_signed(offset.1)?,
+        idx.2.checked_add_signed(offset.2)?,
+    ))
+}
+
+impl From<Direction3D> for (isize, isize, isize) {
+    fn from(value: Direction3D) -> Self {
+        match value {
+            Direction3D::PosX => (1, 0, 0),
+            Direction3D::NegX => (-1, 0, 0),
+            Direction3D::PosY => (0, 1, 0),
+            Direction3D::NegY => (0, -1, 0),
+            Direction3D::PosZ => (0, 0, 1),
+            Direction3D::NegZ => (0, 0, -1),
+        }
+    }
+}
+
+impl TryFrom<(isize, isize, isize)> for Direction3D {
+    type Error = (isize, isize, isize);
+
+    fn try_from(value: (isize, isize, isize)) -> Result<Self, Self::Error> {
+        Direction3D::ALL
+            .into_iter()
+            .find(|direction| <(isize, isize, isize)>::from(*direction) == value)
+            .ok_or(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.counter_clockwise(), Direction::Left);
+        assert_eq!(Direction::UpRight.counter_clockwise(), Direction::UpLeft);
+        assert_eq!(Direction::Left.turn(5), Direction::Up);
+        assert_eq!(Direction::Left.turn(-1), Direction::Down);
+        assert_eq!(Direction::DownLeft.turn(2), Direction::UpRight);
+
+        assert_eq!(
+            Direction::turns_between(Direction::Up, Direction::Up),
+            Some(0)
+        );
+        assert_eq!(
+            Direction::turns_between(Direction::Up, Direction::Left),
+            Some(1)
+        );
+        assert_eq!(
+            Direction::turns_between(Direction::Right, Direction::Left),
+            Some(2)
+        );
+        assert_eq!(
+            Direction::turns_between(Direction::Up, Direction::UpLeft),
+            None
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            Direction::ALL[..4],
+            Direction::CARDINAL,
+            "cardinal directions come first"
+        );
+        assert!(Direction::ALL
+            .iter()
+            .enumerate()
+            .all(|(i, direction)| direction.index() == i));
+        assert!(Direction::DIAGONAL.iter().all(|d| !d.is_cardinal()));
+    }
+
+    #[test]
+    fn test_direction_set() {
+        let mut set = DirectionSet::new();
+        assert!(set.insert(Direction::UpLeft));
+        assert!(!set.insert(Direction::UpLeft));
+        assert!(set.insert(Direction::Down));
+
+        assert!(set.contains(Direction::Down));
+        assert!(!set.contains(Direction::Up));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Direction::Down, Direction::UpLeft]
+        );
+
+        assert_eq!(
+            set | DirectionSet::CARDINAL,
+            DirectionSet::from([
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+                Direction::UpLeft,
+            ])
+        );
+        assert_eq!(
+            set & DirectionSet::DIAGONAL,
+            DirectionSet::from([Direction::UpLeft])
+        );
+        assert!(set.remove(Direction::UpLeft));
+        assert_eq!(
+            DirectionSet::from(Direction::CARDINAL),
+            DirectionSet::CARDINAL
+        );
+    }
+
+    #[test]
+    fn test_hex_direction() {
+        assert_eq!(HexDirection::East.clockwise(), HexDirection::SouthEast);
+        assert_eq!(
+            HexDirection::East.counter_clockwise(),
+            HexDirection::NorthEast
+        );
+        assert_eq!(HexDirection::NorthWest.opposite(), HexDirection::SouthEast);
+        assert_eq!(HexDirection::West.turn(-7), HexDirection::SouthWest);
+        assert_eq!(HexDirection::NorthEast.checked_add((0, 0)), None);
+        assert_eq!(HexDirection::NorthEast.checked_add((2, 3)), Some((3, 2)));
+        assert!(HexDirection::ALL.iter().all(|d| {
+            let (q, r): (isize, isize) = (*d).into();
+            HexDirection::distance((0, 0), (q, r)) == 1
+        }));
+        assert_eq!(HexDirection::distance((0, 0), (2, -3)), 3);
+    }
+
+    #[test]
+    fn test_direction_3d() {
+        assert_eq!(
+            Direction3D::PosX.rotate(Direction3D::PosZ),
+            Direction3D::PosY
+        );
+        assert_eq!(
+            Direction3D::PosY.rotate(Direction3D::PosZ),
+            Direction3D::NegX
+        );
+        assert_eq!(
+            Direction3D::PosZ.rotate(Direction3D::NegZ),
+            Direction3D::PosZ
+        );
+        assert_eq!(Direction3D::NegY.opposite(), Direction3D::PosY);
+        assert_eq!(Direction3D::NegZ.checked_add((1, 1, 0)), None);
+        assert_eq!(Direction3D::PosY.checked_add((1, 1, 0)), Some((1, 2, 0)));
+        assert_eq!(Direction3D::offsets26().count(), 26);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for (notation, direction) in ["^v<>", "UDLR", "nswe"].iter().flat_map(|s| {
+            s.chars().zip([
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ])
+        }) {
+            assert_eq!(Direction::try_from(notation), Ok(direction));
+        }
+
+        assert_eq!(" > ".parse::<Direction>(), Ok(Direction::Right));
+        assert!("<>".parse::<Direction>().is_err());
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!(Direction::Down.to_string(), "v");
+    }
+}
@@ -1,10 +1,113 @@
 use std::{error::Error, fmt::Debug, fmt::Display};
 
-pub struct ParseInputError;
+/// Describes why a puzzle input couldn't be parsed, optionally pointing at
+/// the offending text with a caret-underlined snippet.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct ParseInputError {
+    pub day: Option<u32>,
+    /// 1-based line of the problem, if known.
+    pub line: Option<usize>,
+    /// 1-based column (in characters) of the problem, if known.
+    pub column: Option<usize>,
+    pub expected: String,
+    pub found: Option<String>,
+    /// The full source line containing the problem.
+    pub snippet: Option<String>,
+}
+
+impl ParseInputError {
+    pub fn new(expected: impl Into<String>) -> Self {
+        Self {
+            expected: expected.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    pub fn with_found(mut self, found: impl Into<String>) -> Self {
+        self.found = Some(found.into());
+        self
+    }
+
+    /// Points the error at `len` bytes of `input` starting at byte `offset`.
+    pub fn at(mut self, input: &str, offset: usize, len: usize) -> Self {
+        let offset = offset.min(input.len());
+        let end = (offset + len).min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        self.line = Some(input[..offset].matches('\n').count() + 1);
+        self.column = Some(input[line_start..offset].chars().count() + 1);
+        self.snippet = Some(
+            input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        );
+        if self.found.is_none() {
+            self.found = Some(if offset == end && end == input.len() {
+                "end of input".to_string()
+            } else {
+                format!("{:?}", &input[offset..end])
+            });
+        }
+        self
+    }
+
+    /// Points the error at `token`, which should be a slice of `input`. Falls
+    /// back to the first occurrence of `token` in `input` otherwise.
+    pub fn at_str(self, input: &str, token: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        if token_start >= start && token_start + token.len() <= start + input.len() {
+            return self.at(input, token_start - start, token.len());
+        }
+        match input.find(token) {
+            Some(offset) => self.at(input, offset, token.len()),
+            None => self.with_found(format!("{:?}", token)),
+        }
+    }
+}
 
 impl Display for ParseInputError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Invalid input")
+        write!(f, "Invalid input")?;
+        if let Some(day) = self.day {
+            write!(f, " for day {}", day)?;
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " at line {}, column {}", line, column)?,
+            (Some(line), None) => write!(f, " at line {}", line)?,
+            _ => {}
+        }
+        write!(f, ": expected {}", self.expected)?;
+        if let Some(found) = &self.found {
+            write!(f, ", found {}", found)?;
+        }
+
+        if let (Some(line), Some(column), Some(snippet)) = (self.line, self.column, &self.snippet) {
+            let gutter = line.to_string().len();
+            let width = match &self.found {
+                Some(found) if found.starts_with('"') => found.chars().count().saturating_sub(2),
+                _ => 1,
+            };
+            writeln!(f)?;
+            writeln!(f, "{:gutter$} |", "")?;
+            writeln!(f, "{} | {}", line, snippet)?;
+            write!(
+                f,
+                "{:gutter$} | {}{}",
+                "",
+                " ".repeat(column - 1),
+                "^".repeat(width.max(1))
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -15,3 +118,153 @@ impl Debug for ParseInputError {
 }
 
 impl Error for ParseInputError {}
+
+impl From<ParseGridError> for ParseInputError {
+    fn from(value: ParseGridError) -> Self {
+        match value {
+            ParseGridError::Empty => Self::new("a grid").with_found("end of input"),
+            ParseGridError::RaggedRow {
+                line,
+                column,
+                expected,
+                found,
+            } => Self {
+                line: Some(line),
+                column: Some(column),
+                ..Self::new(format!("{} columns", expected))
+                    .with_found(format!("{} columns", found))
+            },
+            ParseGridError::InvalidCell {
+                line,
+                column,
+                value,
+            } => Self {
+                line: Some(line),
+                column: Some(column),
+                ..Self::new("a grid cell").with_found(format!("{:?}", value))
+            },
+        }
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    RaggedRow {
+        line: usize,
+        column: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        value: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "Invalid grid: input is empty"),
+            ParseGridError::RaggedRow {
+                line,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid grid at {}:{}: expected {} columns, found {}",
+                line, column, expected, found
+            ),
+            ParseGridError::InvalidCell {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "Invalid grid at {}:{}: unexpected character {:?}",
+                line, column, value
+            ),
+        }
+    }
+}
+
+impl Debug for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseGridError {}
+
+#[derive(PartialEq, Eq)]
+pub struct ParseDirectionError(pub String);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid direction: {:?}", self.0)
+    }
+}
+
+impl Debug for ParseDirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "Register A: 1\nRegister B: x2\n";
+        let token = &input[26..28];
+        let error = ParseInputError::new("a number")
+            .with_day(17)
+            .at_str(input, token);
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(13));
+        assert_eq!(error.found.as_deref(), Some("\"x2\""));
+        assert_eq!(
+            error.to_string(),
+            "\
+Invalid input for day 17 at line 2, column 13: expected a number, found \"x2\"
+  |
+2 | Register B: x2
+  |             ^^"
+        );
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let input = "1,2\n";
+        let error = ParseInputError::new("a blank line").at(input, input.len(), 0);
+
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, Some(1));
+        assert_eq!(error.found.as_deref(), Some("end of input"));
+        assert_eq!(
+            ParseInputError::new("x").to_string(),
+            "Invalid input: expected x"
+        );
+    }
+
+    #[test]
+    fn test_from_grid_error() {
+        let error = ParseInputError::from(ParseGridError::InvalidCell {
+            line: 3,
+            column: 4,
+            value: '?',
+        });
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input at line 3, column 4: expected a grid cell, found '?'"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Sample inputs in `fixtures/2024`, with their expected answers in
+//! `fixtures/2024/answers.toml`.
+//!
+//! Samples are named inputs like `day12.sample2.txt`, and every one of them
+//! is solved by the tests below, so adding an example means adding its file
+//! and its table of answers rather than a test function.
+
+use std::path::Path;
+
+use crate::{
+    answers::Answers,
+    inputs::{InputId, InputStore},
+    solutions,
+};
+
+pub const FIXTURES_DIR: &str = "fixtures/2024";
+pub const MANIFEST_FILE: &str = "answers.toml";
+
+#[derive(Debug)]
+pub struct Fixture {
+    pub id: InputId,
+    pub input: String,
+    /// The expected answer for each part that has one.
+    pub expected: Vec<(u32, String)>,
+}
+
+/// Loads every sample in `dir` along with its expected answers. Each sample
+/// needs a table in the manifest, and each table a sample, so neither can be
+/// forgotten.
+pub fn load(dir: impl AsRef<Path>) -> Result<Vec<Fixture>, String> {
+    let dir = dir.as_ref();
+    let manifest = dir.join(MANIFEST_FILE);
+    let answers = Answers::load(&manifest)?;
+    let store = InputStore::open(dir)?;
+    let ids = store.discover()?;
+
+    if let Some(id) = answers.inputs().find(|id| !ids.contains(id)) {
+        return Err(format!(
+            "{} has answers for {}, which doesn't exist",
+            manifest.display(),
+            id
+        ));
+    }
+
+    let mut fixtures = Vec::new();
+    for id in ids {
+        if !answers.inputs().any(|table| *table == id) {
+            return Err(format!("{} has no table for {}", manifest.display(), id));
+        }
+        let expected = (1..=2)
+            .filter_map(|part| Some((part, answers.get_input(&id, part)?.to_string())))
+            .collect();
+        let input = store.read(&id)?;
+        fixtures.push(Fixture {
+            id,
+            input,
+            expected,
+        });
+    }
+    Ok(fixtures)
+}
+
+/// Solves every part of `fixture` that has an expected answer, describing
+/// each one that's wrong or fails.
+pub fn check(fixture: &Fixture) -> Vec<String> {
+    let Some(solution) = solutions::find(fixture.id.day) else {
+        return vec![format!("{}: there is no solution for that day", fixture.id)];
+    };
+
+    let mut failures = Vec::new();
+    for (part, expected) in &fixture.expected {
+        match solution.solve(&fixture.input, *part) {
+            Ok(answer) if answer == *expected => {}
+            Ok(answer) => failures.push(format!(
+                "{} part {}: got {}, expected {}",
+                fixture.id, part, answer, expected
+            )),
+            Err(error) => failures.push(format!("{} part {}: {}", fixture.id, part, error)),
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks every sample of `day` against its expected answers.
+    fn check_fixtures(day: u32) {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_DIR);
+        let failures = load(dir)
+            .unwrap()
+            .iter()
+            .filter(|fixture| fixture.id.day == day)
+            .flat_map(check)
+            .collect::<Vec<_>>();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    macro_rules! fixture_tests {
+        ($($name:ident: $day:literal,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    check_fixtures($day);
+                }
+            )*
+        };
+    }
+
+    fixture_tests! {
+        test_fixtures_day1: 1,
+        test_fixtures_day2: 2,
+        test_fixtures_day3: 3,
+        test_fixtures_day4: 4,
+        test_fixtures_day5: 5,
+        test_fixtures_day6: 6,
+        test_fixtures_day7: 7,
+        test_fixtures_day8: 8,
+        test_fixtures_day9: 9,
+        test_fixtures_day10: 10,
+        test_fixtures_day11: 11,
+        test_fixtures_day12: 12,
+        test_fixtures_day13: 13,
+        test_fixtures_day14: 14,
+        test_fixtures_day15: 15,
+        test_fixtures_day16: 16,
+        test_fixtures_day17: 17,
+        test_fixtures_day18: 18,
+        test_fixtures_day19: 19,
+        test_fixtures_day20: 20,
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_DIR);
+        let fixtures = load(dir).unwrap();
+        let day12 = fixtures
+            .iter()
+            .filter(|fixture| fixture.id.day == 12)
+            .map(|fixture| fixture.id.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            day12,
+            [
+                "day12.sample1.txt",
+                "day12.sample2.txt",
+                "day12.sample3.txt"
+            ]
+        );
+
+        let fixture = Fixture {
+            id: InputId::new(1, Some("sample1")),
+            input: "3   4\n4   3\n".to_string(),
+            expected: vec![(1, "0".to_string()), (2, "8".to_string())],
+        };
+        assert_eq!(
+            check(&fixture),
+            ["day1.sample1.txt part 2: got 7, expected 8"]
+        );
+    }
+}
@@ -0,0 +1,873 @@
+//! Seeded generators of random puzzle inputs, for fuzzing the solutions and
+//! timing them on inputs bigger than the real ones.
+//!
+//! Every generator only produces inputs its day can solve: the guard leaves
+//! its map, the mazes have a way through, the robots draw a tree and the
+//! falling bytes cut the exit off. What `size` counts depends on the day.
+
+use std::{collections::VecDeque, fmt::Write, ops::RangeInclusive};
+
+/// A small SplitMix64 generator. It isn't cryptographic, but it's fast and
+/// the same seed gives the same numbers on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which mustn't be empty. The modulo bias is
+    /// negligible for the small `n` puzzles need.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "below(0)");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "between({}, {})", lo, hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A generator for one day's inputs.
+pub struct Generator {
+    pub day: u32,
+    /// What `size` counts, e.g. "lines".
+    pub size: &'static str,
+    /// The sizes that give a valid input.
+    pub sizes: RangeInclusive<usize>,
+    /// Roughly the size of a real input.
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> Result<String, String> {
+        if !self.sizes.contains(&size) {
+            return Err(format!(
+                "The size of a day {} input is its {}, from {} to {}, not {}",
+                self.day,
+                self.size,
+                self.sizes.start(),
+                self.sizes.end(),
+                size
+            ));
+        }
+        Ok((self.generate)(&mut Rng::new(seed), size))
+    }
+}
+
+fn grid_to_string(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A random cell of `grid` holding `c`, if there is one.
+fn random_cell(rng: &mut Rng, grid: &[Vec<char>], c: char) -> Option<(usize, usize)> {
+    let cells = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &d)| ((i, j), d)))
+        .filter(|&(_, d)| d == c)
+        .map(|(cell, _)| cell)
+        .collect::<Vec<_>>();
+    (!cells.is_empty()).then(|| *rng.choose(&cells))
+}
+
+/// A maze of `#` walls and `.` passages with no loops, `size` rounded up to
+/// an odd number on each side, carved by a randomised depth-first search.
+fn maze(rng: &mut Rng, size: usize) -> Vec<Vec<char>> {
+    let n = size | 1;
+    let mut grid = vec![vec!['#'; n]; n];
+    let mut stack = vec![(1, 1)];
+    grid[1][1] = '.';
+    while let Some(&(i, j)) = stack.last() {
+        let mut next = [(0, 2), (2, 0), (0, -2), (-2, 0)]
+            .into_iter()
+            .map(|(di, dj)| ((i as i64 + di) as usize, (j as i64 + dj) as usize))
+            .filter(|&(ni, nj)| ni > 0 && nj > 0 && ni < n - 1 && nj < n - 1)
+            .filter(|&(ni, nj)| grid[ni][nj] == '#')
+            .collect::<Vec<_>>();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        rng.shuffle(&mut next);
+        let (ni, nj) = next[0];
+        grid[(i + ni) / 2][(j + nj) / 2] = '.';
+        grid[ni][nj] = '.';
+        stack.push((ni, nj));
+    }
+    grid
+}
+
+/// Location IDs, mostly shared between the lists so the similarity score
+/// isn't zero.
+fn day1(rng: &mut Rng, size: usize) -> String {
+    let left = (0..size)
+        .map(|_| rng.between(10000, 99999))
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for &a in &left {
+        let b = if rng.chance(0.4) {
+            *rng.choose(&left)
+        } else {
+            rng.between(10000, 99999)
+        };
+        writeln!(out, "{}   {}", a, b).unwrap();
+    }
+    out
+}
+
+/// Reports that are safe, safe but for one level, or neither.
+fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.between(5, 8) as usize;
+        let sign = if rng.chance(0.5) { 1 } else { -1 };
+        let bad = rng.chance(0.5).then(|| rng.below(len));
+        let mut level = rng.between(20, 70);
+        let mut levels = vec![level];
+        for i in 1..len {
+            let step = if bad == Some(i) {
+                *rng.choose(&[0, -1, -2, 4, 5, 6])
+            } else {
+                rng.between(1, 3)
+            };
+            level += sign * step;
+            levels.push(level);
+        }
+        let levels = levels.iter().map(i64::to_string).collect::<Vec<_>>();
+        writeln!(out, "{}", levels.join(" ")).unwrap();
+    }
+    out
+}
+
+/// Characters and fragments to bury day 3's instructions in, including some
+/// that nearly make instructions.
+const DAY3_NOISE: &str = "%&*@!^[]()<>,?'+-#$:;/ {}~";
+const DAY3_DECOYS: &str =
+    "select() when() where() from() why() mul mul( don't do mul[3,7] mul(32,64] mul(4* mul(6,9!";
+
+/// `size` multiplications buried in noise, with the odd `do()` and `don't()`.
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let noise = DAY3_NOISE.chars().collect::<Vec<_>>();
+    let decoys = DAY3_DECOYS.split(' ').collect::<Vec<_>>();
+    let mut out = String::new();
+    let mut line = String::new();
+    for _ in 0..size {
+        for _ in 0..rng.between(0, 6) {
+            if rng.chance(0.8) {
+                line.push(*rng.choose(&noise));
+            } else {
+                line += *rng.choose(&decoys);
+            }
+        }
+        if rng.chance(0.05) {
+            line += "do()";
+        } else if rng.chance(0.05) {
+            line += "don't()";
+        }
+        write!(line, "mul({},{})", rng.between(1, 999), rng.between(1, 999)).unwrap();
+        if line.len() > 3000 {
+            out += &line;
+            out.push('\n');
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        out += &line;
+        out.push('\n');
+    }
+    out
+}
+
+/// A square word search of the letters in XMAS.
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| *rng.choose(&['X', 'M', 'A', 'S']))
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    grid_to_string(&grid)
+}
+
+/// Rules ordering every pair of 49 pages, and `size` updates of an odd
+/// number of them, about half in order.
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let mut pages = (10..=99).collect::<Vec<i64>>();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut rules = Vec::new();
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            rules.push((pages[i], pages[j]));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut out = String::new();
+    for (before, after) in rules {
+        writeln!(out, "{}|{}", before, after).unwrap();
+    }
+    out.push('\n');
+    for _ in 0..size {
+        let mut update = (0..pages.len()).collect::<Vec<_>>();
+        rng.shuffle(&mut update);
+        update.truncate(2 * rng.between(2, 11) as usize + 1);
+        if rng.chance(0.5) {
+            update.sort();
+        }
+        let update = update
+            .iter()
+            .map(|&i| pages[i].to_string())
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", update.join(",")).unwrap();
+    }
+    out
+}
+
+/// A square lab with scattered obstructions and a guard facing up.
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let mut grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.05) { '#' } else { '.' })
+                .collect()
+        })
+        .collect::<Vec<Vec<_>>>();
+    let (i, j) = (rng.below(size), rng.below(size));
+    grid[i][j] = '^';
+    grid_to_string(&grid)
+}
+
+/// Calibration equations, about half of which some operators make true.
+/// Their operands have 14 digits between them at most, so even
+/// concatenating them all fits in a `u64`.
+fn day7(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut numbers = Vec::new();
+        let mut digits = 0;
+        while numbers.len() < 12 {
+            let len: u32 = *rng.choose(&[1, 1, 1, 2, 3]);
+            if digits + len > 14 {
+                break;
+            }
+            digits += len;
+            numbers.push(rng.between(10i64.pow(len - 1), 10i64.pow(len) - 1) as u64);
+        }
+        if numbers.len() < 2 {
+            numbers.push(rng.between(1, 9) as u64);
+        }
+
+        let mut value = numbers[0];
+        for &n in &numbers[1..] {
+            value = match rng.below(3) {
+                0 => value + n,
+                1 => value * n,
+                _ => value * 10u64.pow(n.to_string().len() as u32) + n,
+            };
+        }
+        if rng.chance(0.5) {
+            value += rng.between(1, 9) as u64;
+        }
+
+        let numbers = numbers.iter().map(u64::to_string).collect::<Vec<_>>();
+        writeln!(out, "{}: {}", value, numbers.join(" ")).unwrap();
+    }
+    out
+}
+
+/// A square map with a few antennas of each frequency.
+fn day8(rng: &mut Rng, size: usize) -> String {
+    let frequencies = ('0'..='9')
+        .chain('a'..='z')
+        .chain('A'..='Z')
+        .collect::<Vec<_>>();
+    let mut grid = vec![vec!['.'; size]; size];
+    for &frequency in &frequencies[..(size * size / 60).clamp(1, frequencies.len())] {
+        for _ in 0..rng.between(2, 4) {
+            if let Some((i, j)) = random_cell(rng, &grid, '.') {
+                grid[i][j] = frequency;
+            }
+        }
+    }
+    grid_to_string(&grid)
+}
+
+/// A disk map of `size` files.
+fn day9(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for file in 0..size {
+        if file > 0 {
+            write!(out, "{}", rng.between(0, 9)).unwrap();
+        }
+        write!(out, "{}", rng.between(1, 9)).unwrap();
+    }
+    out + "\n"
+}
+
+/// A square topographic map of random heights with hiking trails carved
+/// through it.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let mut grid = (0..size)
+        .map(|_| (0..size).map(|_| rng.between(0, 9)).collect())
+        .collect::<Vec<Vec<_>>>();
+    for _ in 0..size * size / 20 {
+        let (mut i, mut j) = (rng.below(size), rng.below(size));
+        for height in 0..=9 {
+            grid[i][j] = height;
+            let (di, dj) = *rng.choose(&[(0, 1), (1, 0), (0, -1), (-1, 0)]);
+            let (ni, nj) = (i as i64 + di, j as i64 + dj);
+            if ni < 0 || nj < 0 || ni >= size as i64 || nj >= size as i64 {
+                break;
+            }
+            (i, j) = (ni as usize, nj as usize);
+        }
+    }
+    let grid = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&height| char::from_digit(height as u32, 10).unwrap())
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    grid_to_string(&grid)
+}
+
+/// `size` stones of up to seven digits.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let stones = (0..size)
+        .map(|_| {
+            let digits = rng.between(1, 7) as u32;
+            let lowest = if digits == 1 {
+                0
+            } else {
+                10i64.pow(digits - 1)
+            };
+            rng.between(lowest, 10i64.pow(digits) - 1).to_string()
+        })
+        .collect::<Vec<_>>();
+    stones.join(" ") + "\n"
+}
+
+/// A square garden of regions grown around random seeds.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let seeds = (0..(size * size / 40).max(1))
+        .map(|_| {
+            let plant = *rng.choose(&('A'..='Z').collect::<Vec<_>>());
+            (rng.below(size) as i64, rng.below(size) as i64, plant)
+        })
+        .collect::<Vec<_>>();
+    let grid = (0..size as i64)
+        .map(|i| {
+            (0..size as i64)
+                .map(|j| {
+                    let noise = rng.between(0, 2);
+                    seeds
+                        .iter()
+                        .min_by_key(|&&(si, sj, _)| (si - i).abs() + (sj - j).abs() + noise)
+                        .unwrap()
+                        .2
+                })
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    grid_to_string(&grid)
+}
+
+/// Claw machines whose buttons are never parallel, about half of which can
+/// win their prize.
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let mut machines = Vec::new();
+    for _ in 0..size {
+        let (a, b) = loop {
+            let a = (rng.between(10, 99), rng.between(10, 99));
+            let b = (rng.between(10, 99), rng.between(10, 99));
+            if a.0 * b.1 != a.1 * b.0 {
+                break (a, b);
+            }
+        };
+        let prize = if rng.chance(0.5) {
+            let (na, nb) = (rng.between(1, 100), rng.between(1, 100));
+            (na * a.0 + nb * b.0, na * a.1 + nb * b.1)
+        } else {
+            (rng.between(1000, 20000), rng.between(1000, 20000))
+        };
+        machines.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+    machines.join("\n")
+}
+
+/// Robots that line up into a framed Christmas tree at a random second,
+/// plus `size` more that don't.
+fn day14(rng: &mut Rng, size: usize) -> String {
+    const WIDTH: i64 = 101;
+    const HEIGHT: i64 = 103;
+
+    let (left, top) = (rng.between(0, WIDTH - 31), rng.between(0, HEIGHT - 33));
+    let mut tree = Vec::new();
+    for y in 0..33 {
+        for x in 0..31 {
+            let frame = x == 0 || x == 30 || y == 0 || y == 32;
+            let crown = (2..27).contains(&y) && (x - 15i64).abs() <= (y - 2) / 2;
+            let trunk = (27..30).contains(&y) && (14..=16).contains(&x);
+            if frame || crown || trunk {
+                tree.push((left + x, top + y));
+            }
+        }
+    }
+
+    let seconds = rng.between(1, WIDTH * HEIGHT - 1);
+    let mut robots = Vec::new();
+    for &(x, y) in &tree {
+        let velocity = (rng.between(-100, 100), rng.between(-100, 100));
+        let start = (
+            (x - velocity.0 * seconds).rem_euclid(WIDTH),
+            (y - velocity.1 * seconds).rem_euclid(HEIGHT),
+        );
+        robots.push((start, velocity));
+    }
+    for _ in 0..size {
+        let start = (rng.between(0, WIDTH - 1), rng.between(0, HEIGHT - 1));
+        robots.push((start, (rng.between(-100, 100), rng.between(-100, 100))));
+    }
+    rng.shuffle(&mut robots);
+
+    let mut out = String::new();
+    for ((x, y), (vx, vy)) in robots {
+        writeln!(out, "p={},{} v={},{}", x, y, vx, vy).unwrap();
+    }
+    out
+}
+
+/// A walled square warehouse full of boxes, and eight moves per tile.
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let mut grid = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    if i == 0 || j == 0 || i == size - 1 || j == size - 1 || rng.chance(0.08) {
+                        '#'
+                    } else if rng.chance(0.35) {
+                        'O'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<_>>>();
+    grid[size / 2][size / 2] = '@';
+
+    let moves = (0..size * size * 8)
+        .map(|_| *rng.choose(&['<', '>', '^', 'v']))
+        .collect::<Vec<_>>();
+    let mut out = grid_to_string(&grid) + "\n";
+    for line in moves.chunks(1000) {
+        out.extend(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// A square maze with a few loops, from the bottom left to the top right.
+fn day16(rng: &mut Rng, size: usize) -> String {
+    let mut grid = maze(rng, size);
+    let n = grid.len();
+    for (i, row) in grid.iter_mut().enumerate().take(n - 1).skip(1) {
+        for (j, cell) in row.iter_mut().enumerate().take(n - 1).skip(1) {
+            // Walls between two passages in a row or column.
+            let between = (i % 2 == 1) != (j % 2 == 1);
+            if between && *cell == '#' && rng.chance(0.1) {
+                *cell = '.';
+            }
+        }
+    }
+    grid[n - 2][1] = 'S';
+    grid[1][n - 2] = 'E';
+    grid_to_string(&grid)
+}
+
+/// A program shaped like the puzzle's: a loop that outputs a hash of the
+/// low bits of A and shifts them out, so it always halts. `size` is how
+/// many octal digits A starts with, and so how many numbers it outputs.
+/// Part 2 only has a solution for some programs.
+fn day17(rng: &mut Rng, size: usize) -> String {
+    let mut body = vec![[1, rng.between(0, 7)], [4, rng.between(0, 7)]];
+    rng.shuffle(&mut body);
+    body.insert(rng.below(body.len() + 1), [0, 3]);
+
+    let mut program = vec![[2, 4], [1, rng.between(0, 7)], [7, 5]];
+    program.extend(body);
+    program.extend([[5, 5], [3, 0]]);
+    let program = program
+        .iter()
+        .flatten()
+        .map(i64::to_string)
+        .collect::<Vec<_>>();
+
+    let a = rng.between(8i64.pow(size as u32 - 1), 8i64.pow(size as u32) - 1);
+    format!(
+        "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+        a,
+        program.join(",")
+    )
+}
+
+/// Whether (70, 70) can be reached from (0, 0) in a 71 by 71 memory space
+/// with `blocked` bytes fallen.
+fn day18_has_path(blocked: &[(usize, usize)]) -> bool {
+    let mut grid = [[false; 71]; 71];
+    for &(x, y) in blocked {
+        grid[y][x] = true;
+    }
+    let mut queue = VecDeque::from([(0, 0)]);
+    grid[0][0] = true;
+    while let Some((x, y)) = queue.pop_front() {
+        if (x, y) == (70, 70) {
+            return true;
+        }
+        for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            if (0..71).contains(&nx) && (0..71).contains(&ny) && !grid[ny as usize][nx as usize] {
+                grid[ny as usize][nx as usize] = true;
+                queue.push_back((nx as usize, ny as usize));
+            }
+        }
+    }
+    false
+}
+
+/// `size` bytes falling on distinct tiles, leaving a path after the first
+/// 1024. If `size` bytes don't cut the exit off, more fall until they do.
+fn day18(rng: &mut Rng, size: usize) -> String {
+    let mut bytes = (0..71)
+        .flat_map(|x| (0..71).map(move |y| (x, y)))
+        .filter(|&tile| tile != (0, 0) && tile != (70, 70))
+        .collect::<Vec<_>>();
+    loop {
+        rng.shuffle(&mut bytes);
+        if day18_has_path(&bytes[..1024]) {
+            break;
+        }
+    }
+
+    // The first byte count that cuts the exit off.
+    let (mut low, mut high) = (1024, bytes.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if day18_has_path(&bytes[..mid]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    let mut out = String::new();
+    for (x, y) in &bytes[..size.max(low)] {
+        writeln!(out, "{},{}", x, y).unwrap();
+    }
+    out
+}
+
+/// Towel patterns including all but one single colour, and `size` designs,
+/// about half of them made of the patterns.
+fn day19(rng: &mut Rng, size: usize) -> String {
+    const COLOURS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+    let mut colours = COLOURS;
+    rng.shuffle(&mut colours);
+
+    let mut patterns = colours[1..].iter().map(char::to_string).collect::<Vec<_>>();
+    while patterns.len() < 150 {
+        let len = rng.between(2, 8);
+        let pattern = (0..len).map(|_| *rng.choose(&COLOURS)).collect::<String>();
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+    rng.shuffle(&mut patterns);
+
+    let mut out = patterns.join(", ") + "\n\n";
+    for _ in 0..size {
+        let len = rng.between(20, 50) as usize;
+        let mut design = String::new();
+        if rng.chance(0.5) {
+            while design.len() < len {
+                design += rng.choose(&patterns).as_str();
+            }
+        } else {
+            design = (0..len).map(|_| *rng.choose(&COLOURS)).collect();
+        }
+        design.truncate(50);
+        writeln!(out, "{}", design).unwrap();
+    }
+    out
+}
+
+/// A square maze with no loops, from the bottom left to the top right.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    let mut grid = maze(rng, size);
+    let n = grid.len();
+    grid[n - 2][1] = 'S';
+    grid[1][n - 2] = 'E';
+    grid_to_string(&grid)
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        size: "number of lines",
+        sizes: 1..=10_000,
+        default_size: 1000,
+        generate: day1,
+    },
+    Generator {
+        day: 2,
+        size: "number of reports",
+        sizes: 1..=100_000,
+        default_size: 1000,
+        generate: day2,
+    },
+    Generator {
+        day: 3,
+        size: "number of multiplications",
+        sizes: 1..=2000,
+        default_size: 700,
+        generate: day3,
+    },
+    Generator {
+        day: 4,
+        size: "width and height",
+        sizes: 1..=1000,
+        default_size: 140,
+        generate: day4,
+    },
+    Generator {
+        day: 5,
+        size: "number of updates",
+        sizes: 1..=10_000,
+        default_size: 200,
+        generate: day5,
+    },
+    Generator {
+        day: 6,
+        size: "width and height",
+        sizes: 1..=1000,
+        default_size: 130,
+        generate: day6,
+    },
+    Generator {
+        day: 7,
+        size: "number of equations",
+        sizes: 1..=10_000,
+        default_size: 850,
+        generate: day7,
+    },
+    Generator {
+        day: 8,
+        size: "width and height",
+        sizes: 1..=1000,
+        default_size: 50,
+        generate: day8,
+    },
+    Generator {
+        day: 9,
+        size: "number of files",
+        sizes: 1..=100_000,
+        default_size: 10_000,
+        generate: day9,
+    },
+    Generator {
+        day: 10,
+        size: "width and height",
+        sizes: 1..=1000,
+        default_size: 50,
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        size: "number of stones",
+        sizes: 1..=1000,
+        default_size: 8,
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        size: "width and height",
+        sizes: 1..=1000,
+        default_size: 140,
+        generate: day12,
+    },
+    Generator {
+        day: 13,
+        size: "number of machines",
+        sizes: 1..=10_000,
+        default_size: 320,
+        generate: day13,
+    },
+    Generator {
+        day: 14,
+        size: "number of robots outside the tree",
+        sizes: 0..=2000,
+        default_size: 170,
+        generate: day14,
+    },
+    Generator {
+        day: 15,
+        size: "width and height",
+        sizes: 3..=200,
+        default_size: 50,
+        generate: day15,
+    },
+    Generator {
+        day: 16,
+        size: "width and height, rounded up to odd",
+        sizes: 5..=501,
+        default_size: 141,
+        generate: day16,
+    },
+    Generator {
+        day: 17,
+        size: "number of octal digits in register A",
+        sizes: 1..=10,
+        default_size: 10,
+        generate: day17,
+    },
+    Generator {
+        day: 18,
+        size: "number of falling bytes",
+        sizes: 1024..=5039,
+        default_size: 3450,
+        generate: day18,
+    },
+    Generator {
+        day: 19,
+        size: "number of designs",
+        sizes: 1..=10_000,
+        default_size: 400,
+        generate: day19,
+    },
+    Generator {
+        day: 20,
+        size: "width and height, rounded up to odd",
+        sizes: 5..=501,
+        default_size: 141,
+        generate: day20,
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions;
+
+    #[test]
+    fn test_rng() {
+        // The first SplitMix64 output for seed 0.
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((-2..=2).contains(&rng.between(-2, 2)));
+        }
+        let mut items = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_generate() {
+        let generator = find(1).unwrap();
+        assert_eq!(
+            generator.generate(5, 20).unwrap(),
+            generator.generate(5, 20).unwrap()
+        );
+        assert_ne!(
+            generator.generate(5, 20).unwrap(),
+            generator.generate(6, 20).unwrap()
+        );
+        assert_eq!(generator.generate(5, 20).unwrap().lines().count(), 20);
+        assert!(generator.generate(5, 0).is_err());
+        assert!(find(18).unwrap().generate(5, 10).is_err());
+    }
+
+    /// Solves small generated inputs of every day, which must all succeed.
+    #[test]
+    fn test_generated_inputs_solve() {
+        assert!(GENERATORS
+            .iter()
+            .enumerate()
+            .all(|(i, g)| g.day == i as u32 + 1));
+
+        for generator in GENERATORS {
+            let solution = solutions::find(generator.day).unwrap();
+            let size = (*generator.sizes.start()).max(10);
+            for seed in 0..3 {
+                let input = generator.generate(seed, size).unwrap();
+                // Day 17 part 2 needs z3 and a program that can output
+                // itself.
+                let parts = if generator.day == 17 { 1 } else { 2 };
+                for part in 1..=parts {
+                    if let Err(error) = solution.solve(&input, part) {
+                        panic!(
+                            "Day {} part {} failed on seed {}: {}\n{}",
+                            generator.day, part, seed, error, input
+                        );
+                    }
+                }
+            }
+        }
+    }
+    /// Random prizes can be reached with negative presses, which mustn't
+    /// count towards the total.
+    #[test]
+    fn test_day13_totals() {
+        let input = find(13).unwrap().generate(7, 10000).unwrap();
+        let solution = solutions::find(13).unwrap();
+        for part in 1..=2 {
+            let total = solution.solve(&input, part).unwrap();
+            assert!(!total.starts_with('-'), "part {} total {}", part, total);
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! The local store of puzzle inputs in `input/2024`.
+//!
+//! A day can have several named inputs: `day6.txt` is the default one that
+//! cargo-aoc downloads, while `day6.alt.txt` and `day6.sample1.txt` are named
+//! `alt` and `sample1`. `checksums.txt` records the size and SHA-256 of each
+//! input, so a truncated or mangled copy is caught before it gives a wrong
+//! answer.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::PathBuf,
+};
+
+use crate::{errors::ParseInputError, parse, sha256::sha256_hex};
+
+pub const INPUT_DIR: &str = "input/2024";
+pub const CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// Which input of which day, ordered by day with the default input first.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputId {
+    pub day: u32,
+    /// `None` for the default input.
+    pub name: Option<String>,
+}
+
+/// Whether `name` can name an input: letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl InputId {
+    pub fn new(day: u32, name: Option<&str>) -> Self {
+        Self {
+            day,
+            name: name.map(str::to_string),
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        match &self.name {
+            Some(name) => format!("day{}.{}.txt", self.day, name),
+            None => format!("day{}.txt", self.day),
+        }
+    }
+
+    /// The input stored as `file_name`, if it's named like one.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let stem = file_name.strip_prefix("day")?.strip_suffix(".txt")?;
+        let (day, name) = match stem.split_once('.') {
+            Some((day, name)) => (day, Some(name)),
+            None => (stem, None),
+        };
+        let day = day.parse().ok().filter(|day| (1..=25).contains(day))?;
+        if name.is_some_and(|name| !is_valid_name(name)) {
+            return None;
+        }
+        Some(Self::new(day, name))
+    }
+}
+
+impl Display for InputId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.file_name())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    pub len: usize,
+    /// Lowercase hex.
+    pub sha256: String,
+}
+
+impl Checksum {
+    pub fn of(contents: &[u8]) -> Self {
+        Self {
+            len: contents.len(),
+            sha256: sha256_hex(contents),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    Empty,
+    /// Windows line endings, which the solutions don't expect.
+    Crlf,
+    /// Shorter than the recorded input.
+    Truncated {
+        len: usize,
+        expected: usize,
+    },
+    /// Different from the recorded input.
+    Changed,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "the input is empty"),
+            Problem::Crlf => write!(f, "the input has CRLF line endings"),
+            Problem::Truncated { len, expected } => write!(
+                f,
+                "the input is truncated: {} bytes, but {} were recorded",
+                len, expected
+            ),
+            Problem::Changed => write!(f, "the input doesn't match its recorded checksum"),
+        }
+    }
+}
+
+/// Checks `contents` against its `recorded` checksum, if there is one.
+pub fn check(contents: &[u8], recorded: Option<&Checksum>) -> Result<(), Problem> {
+    if contents.is_empty() {
+        return Err(Problem::Empty);
+    }
+    let crlf = contents.windows(2).any(|pair| pair == b"\r\n");
+
+    let Some(recorded) = recorded else {
+        return if crlf { Err(Problem::Crlf) } else { Ok(()) };
+    };
+    if Checksum::of(contents) == *recorded {
+        return Ok(());
+    }
+
+    let normalised = String::from_utf8_lossy(contents).replace("\r\n", "\n");
+    if crlf && Checksum::of(normalised.as_bytes()) == *recorded {
+        Err(Problem::Crlf)
+    } else if contents.len() < recorded.len {
+        Err(Problem::Truncated {
+            len: contents.len(),
+            expected: recorded.len,
+        })
+    } else {
+        Err(Problem::Changed)
+    }
+}
+
+/// Parses `checksums.txt`: a `file size sha256` line per input, with `#`
+/// comments on their own lines.
+pub fn parse_checksums(input: &str) -> Result<BTreeMap<InputId, Checksum>, ParseInputError> {
+    let mut checksums = BTreeMap::new();
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let &[file_name, len, sha256] = fields.as_slice() else {
+            return Err(ParseInputError::new("a file name, size and SHA-256")
+                .at_str(input, line)
+                .with_found(format!("{} fields", fields.len())));
+        };
+        let id = InputId::from_file_name(file_name).ok_or_else(|| {
+            ParseInputError::new("an input file name like day6.txt or day6.alt.txt")
+                .at_str(input, file_name)
+        })?;
+        let len = parse::token(input, len, "a size in bytes")?;
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseInputError::new("a SHA-256 in hex").at_str(input, sha256));
+        }
+
+        let sha256 = sha256.to_ascii_lowercase();
+        checksums.insert(id, Checksum { len, sha256 });
+    }
+    Ok(checksums)
+}
+
+/// The inputs in a directory, with their recorded checksums.
+#[derive(Debug)]
+pub struct InputStore {
+    dir: PathBuf,
+    checksums: BTreeMap<InputId, Checksum>,
+}
+
+impl InputStore {
+    /// Opens the inputs in `dir`, loading its `checksums.txt`. A missing
+    /// checksums file is treated as empty.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, String> {
+        let dir = dir.into();
+        let path = dir.join(CHECKSUMS_FILE);
+        let checksums = match fs::read_to_string(&path) {
+            Ok(input) => {
+                parse_checksums(&input).map_err(|e| format!("{}: {}", path.display(), e))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("Couldn't read {}: {}", path.display(), e)),
+        };
+        Ok(Self { dir, checksums })
+    }
+
+    pub fn path(&self, id: &InputId) -> PathBuf {
+        self.dir.join(id.file_name())
+    }
+
+    pub fn exists(&self, id: &InputId) -> bool {
+        self.path(id).is_file()
+    }
+
+    pub fn checksum(&self, id: &InputId) -> Option<&Checksum> {
+        self.checksums.get(id)
+    }
+
+    /// Every input in the directory, by day with the default input first.
+    /// A missing directory has no inputs.
+    pub fn discover(&self) -> Result<Vec<InputId>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Couldn't read {}: {}", self.dir.display(), e)),
+        };
+
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| format!("Couldn't read {}: {}", self.dir.display(), e))?;
+            if let Some(id) = entry.file_name().to_str().and_then(InputId::from_file_name) {
+                ids.push(id);
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn read_bytes(&self, id: &InputId) -> Result<Vec<u8>, String> {
+        let path = self.path(id);
+        fs::read(&path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))
+    }
+
+    /// Checks the input against its recorded checksum, if there is one.
+    pub fn verify(&self, id: &InputId) -> Result<Result<(), Problem>, String> {
+        Ok(check(&self.read_bytes(id)?, self.checksum(id)))
+    }
+
+    /// Reads the input, failing if it's empty, has CRLF line endings or
+    /// doesn't match its recorded checksum.
+    pub fn read(&self, id: &InputId) -> Result<String, String> {
+        let path = self.path(id);
+        let contents = self.read_bytes(id)?;
+        check(&contents, self.checksum(id)).map_err(|problem| {
+            let hint = match problem {
+                Problem::Truncated { .. } | Problem::Changed => {
+                    ". Fetch it again, or run `checksum` if it was meant to change"
+                }
+                Problem::Empty | Problem::Crlf => "",
+            };
+            format!("{}: {}{}", path.display(), problem, hint)
+        })?;
+        String::from_utf8(contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Records the input's current size and checksum, replacing any
+    /// recorded before. Empty and CRLF inputs are refused rather than
+    /// recorded. [`InputStore::save`] writes them out.
+    pub fn record(&mut self, id: &InputId) -> Result<&Checksum, String> {
+        let contents = self.read_bytes(id)?;
+        check(&contents, None)
+            .map_err(|problem| format!("{}: {}", self.path(id).display(), problem))?;
+        let checksum = Checksum::of(&contents);
+        self.checksums.insert(id.clone(), checksum);
+        Ok(&self.checksums[id])
+    }
+
+    /// Writes the recorded checksums to `checksums.txt`.
+    pub fn save(&self) -> Result<(), String> {
+        let mut out = String::from("# file, size in bytes, SHA-256\n");
+        for (id, checksum) in &self.checksums {
+            out += &format!("{} {} {}\n", id, checksum.len, checksum.sha256);
+        }
+        let path = self.dir.join(CHECKSUMS_FILE);
+        fs::write(&path, out).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn test_file_names() {
+        let id = InputId::from_file_name("day6.txt").unwrap();
+        assert_eq!(id, InputId::new(6, None));
+        assert_eq!(id.file_name(), "day6.txt");
+
+        let id = InputId::from_file_name("day6.sample1.txt").unwrap();
+        assert_eq!(id, InputId::new(6, Some("sample1")));
+        assert_eq!(id.file_name(), "day6.sample1.txt");
+
+        for file_name in [
+            "day0.txt",
+            "day26.txt",
+            "day6",
+            "dayx.txt",
+            "day6..txt",
+            "day6.a.b.txt",
+            "day6.a b.txt",
+            "checksums.txt",
+        ] {
+            assert_eq!(InputId::from_file_name(file_name), None, "{}", file_name);
+        }
+
+        let mut ids = [
+            InputId::new(10, None),
+            InputId::new(2, Some("alt")),
+            InputId::new(2, None),
+        ];
+        ids.sort();
+        assert_eq!(
+            ids.map(|id| id.file_name()),
+            ["day2.txt", "day2.alt.txt", "day10.txt"]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let recorded = Checksum::of(b"1 2\n3 4\n");
+        assert_eq!(check(b"1 2\n3 4\n", Some(&recorded)), Ok(()));
+        assert_eq!(check(b"1 2\n3 4\n", None), Ok(()));
+        assert_eq!(check(b"", Some(&recorded)), Err(Problem::Empty));
+        assert_eq!(
+            check(b"1 2\r\n3 4\r\n", Some(&recorded)),
+            Err(Problem::Crlf)
+        );
+        assert_eq!(check(b"1 2\r\n3 4\r\n", None), Err(Problem::Crlf));
+        assert_eq!(
+            check(b"1 2\n3", Some(&recorded)),
+            Err(Problem::Truncated {
+                len: 5,
+                expected: 8
+            })
+        );
+        assert_eq!(check(b"1 2\n3 5\n", Some(&recorded)), Err(Problem::Changed));
+    }
+
+    #[test]
+    fn test_parse_checksums() {
+        let sha256 = "ab".repeat(32);
+        let input = format!(
+            "# comment\nday6.txt 12 {}\nday6.alt.txt 3 {}\n",
+            sha256,
+            sha256.to_uppercase()
+        );
+        let checksums = parse_checksums(&input).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(
+            checksums[&InputId::new(6, Some("alt"))],
+            Checksum {
+                len: 3,
+                sha256: sha256.clone()
+            }
+        );
+
+        let err = parse_checksums(&format!("day6.txt {}", sha256)).unwrap_err();
+        assert_eq!(err.expected, "a file name, size and SHA-256");
+        let err = parse_checksums(&format!("notes.txt 12 {}", sha256)).unwrap_err();
+        assert_eq!(err.column, Some(1));
+        let err = parse_checksums(&format!("day6.txt twelve {}", sha256)).unwrap_err();
+        assert_eq!(err.column, Some(10));
+        let err = parse_checksums("day6.txt 12 abc").unwrap_err();
+        assert_eq!(err.expected, "a SHA-256 in hex");
+    }
+
+    #[test]
+    fn test_store() {
+        let dir = env::temp_dir().join(format!("aoc-2024-inputs-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day2.txt"), "1 2\n3 4\n").unwrap();
+        fs::write(dir.join("day2.sample1.txt"), "1 2\r\n").unwrap();
+        fs::write(dir.join("day10.txt"), "0123\n").unwrap();
+        fs::write(dir.join("notes.md"), "").unwrap();
+
+        let mut store = InputStore::open(&dir).unwrap();
+        let ids = store.discover().unwrap();
+        assert_eq!(
+            ids,
+            [
+                InputId::new(2, None),
+                InputId::new(2, Some("sample1")),
+                InputId::new(10, None)
+            ]
+        );
+        assert_eq!(store.read(&ids[0]).unwrap(), "1 2\n3 4\n");
+        assert!(store.read(&ids[1]).unwrap_err().contains("CRLF"));
+
+        assert!(store.record(&ids[1]).is_err());
+        store.record(&ids[0]).unwrap();
+        store.record(&ids[2]).unwrap();
+        store.save().unwrap();
+        fs::write(dir.join("day2.txt"), "1 2\n").unwrap();
+
+        let store = InputStore::open(&dir).unwrap();
+        assert_eq!(store.checksum(&ids[0]).unwrap().len, 8);
+        assert_eq!(store.checksum(&ids[1]), None);
+        assert_eq!(
+            store.verify(&ids[0]).unwrap(),
+            Err(Problem::Truncated {
+                len: 4,
+                expected: 8
+            })
+        );
+        assert!(store.read(&ids[0]).unwrap_err().contains("truncated"));
+        assert_eq!(store.read(&ids[2]).unwrap(), "0123\n");
+        assert!(store.read(&InputId::new(3, None)).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
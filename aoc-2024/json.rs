@@ -0,0 +1,124 @@
+//! Just enough hand-written JSON for the runner's machine-readable output.
+//!
+//! Objects keep their fields in insertion order and render on a single line,
+//! so the same results always serialise to the same text.
+
+use std::fmt::Write;
+
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> String {
+        let mut out = String::with_capacity(self.len() + 2);
+        out.push('"');
+        for c in self.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> String {
+        self.as_str().to_json()
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> String {
+        self.to_string()
+    }
+}
+
+macro_rules! number_to_json {
+    ($($t:ty),*) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+number_to_json!(u32, u64, u128, usize);
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> String {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> String {
+        match self {
+            Some(value) => value.to_json(),
+            None => "null".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Object {
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, key: &'static str, value: impl ToJson) -> Self {
+        self.fields.push((key, value.to_json()));
+        self
+    }
+}
+
+impl ToJson for Object {
+    fn to_json(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key.to_json(), value))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", fields.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings() {
+        assert_eq!("plain".to_json(), "\"plain\"");
+        assert_eq!("a \"b\"\\c".to_json(), r#""a \"b\"\\c""#);
+        assert_eq!("line\nnext\u{1}".to_json(), r#""line\nnext\u0001""#);
+    }
+
+    #[test]
+    fn test_object() {
+        let object = Object::new()
+            .field("day", 6u32)
+            .field("answer", Some("41"))
+            .field("error", None::<String>)
+            .field("nested", Object::new().field("ok", true));
+        assert_eq!(
+            object.to_json(),
+            r#"{"day": 6, "answer": "41", "error": null, "nested": {"ok": true}}"#
+        );
+        assert_eq!(Object::new().to_json(), "{}");
+    }
+}
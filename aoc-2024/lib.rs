@@ -3,6 +3,9 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod answers;
+pub mod bench;
+pub mod bitgrid;
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -25,6 +28,19 @@ pub mod day8;
 pub mod day9;
 pub mod direction;
 pub mod errors;
+pub mod fixtures;
+pub mod gen;
+pub mod inputs;
+pub mod json;
+pub mod parse;
+pub mod point;
+pub mod pool;
+pub mod sha256;
+pub mod solutions;
+pub mod sparse;
+pub mod table;
+pub mod torus;
 pub mod vec2d;
+pub mod vec3d;
 
 aoc_lib! { year = 2024 }
@@ -1,6 +1,835 @@
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
+use std::{
+    env, fs,
+    io::{self, Read},
+    num::NonZeroUsize,
+    path::PathBuf,
+    process::ExitCode,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
-use aoc_runner_derive::aoc_main;
+use advent_of_code_2024::{
+    answers::{Answers, ANSWERS_PATH},
+    bench::{self, Config, Record},
+    gen::{self, GENERATORS},
+    inputs::{self, InputId, InputStore, Problem, INPUT_DIR},
+    json::{Object, ToJson},
+    pool::{self, Job, Outcome},
+    sha256::sha256_hex,
+    solutions::{self, Puzzle, Run, SOLUTIONS},
+    table::{self, Align},
+};
 
-aoc_main! { lib = advent_of_code_2024 }
+const USAGE: &str = "\
+Usage: aoc-2024 [run|bench|check|list|checksum|generate] [options]
+
+Commands:
+    run      Solve the selected puzzles and print the answers (default)
+    bench    Time parsing and solving the selected puzzles, print a table
+             of the results and save them as JSON
+    check    Solve the selected puzzles and compare against the known
+             answers in answers/2024.toml
+    list     List every input in input/2024 and whether it matches its
+             recorded checksum
+    checksum Record the size and SHA-256 of the selected inputs in
+             input/2024/checksums.txt
+    generate Print a random input for --day, or save it with --name and
+             record its checksum. Without --day, list what --size means
+             for each day
+
+Options:
+    --day <N>           Only this day (default: every solved day)
+    --part <1|2>        Only this part (default: both)
+    --name <name>       Use the named input input/2024/dayN.<name>.txt,
+                        skipping days without one (default: dayN.txt)
+    --input <path|->    Read the input from a file, or stdin for `-`.
+                        Needs --day. Isn't checked against checksums
+    --save <path>       Where bench saves its results
+                        (default: target/bench-2024.json)
+    --json              Print one JSON object per day and part instead of
+                        text, for run and check
+    --all               Run every part in parallel and print a summary
+                        table, for run
+    --jobs <N>          How many parts --all runs at once
+                        (default: the number of CPUs)
+    --timeout <secs>    Give up on a part after this long with --all
+                        (default: 60)
+    --size <N>          How big an input generate makes
+                        (default: about the size of a real input)
+    --seed <N>          The seed generate starts from (default: 0)
+    -h, --help          Print this message
+";
+
+const BENCH_PATH: &str = "target/bench-2024.json";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Command {
+    Run,
+    Bench,
+    Check,
+    List,
+    Checksum,
+    Generate,
+    Help,
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    day: Option<u32>,
+    part: Option<u32>,
+    name: Option<String>,
+    input: Option<String>,
+    save: Option<String>,
+    json: bool,
+    all: bool,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+    size: Option<usize>,
+    seed: Option<u64>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        command: Command::Run,
+        day: None,
+        part: None,
+        name: None,
+        input: None,
+        save: None,
+        json: false,
+        all: false,
+        jobs: None,
+        timeout: None,
+        size: None,
+        seed: None,
+    };
+
+    let mut first = true;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "run" if first => options.command = Command::Run,
+            "bench" if first => options.command = Command::Bench,
+            "check" if first => options.command = Command::Check,
+            "list" if first => options.command = Command::List,
+            "checksum" if first => options.command = Command::Checksum,
+            "generate" if first => options.command = Command::Generate,
+            "--day" => {
+                let day = value("--day")?;
+                let day = day
+                    .parse()
+                    .ok()
+                    .filter(|day| solutions::find(*day).is_some())
+                    .ok_or(format!("No solution for day {:?}", day))?;
+                options.day = Some(day);
+            }
+            "--part" => {
+                let part = value("--part")?;
+                let part = part
+                    .parse()
+                    .ok()
+                    .filter(|part| (1..=2).contains(part))
+                    .ok_or(format!("--part must be 1 or 2, not {:?}", part))?;
+                options.part = Some(part);
+            }
+            "--name" => {
+                let name = value("--name")?;
+                if !inputs::is_valid_name(&name) {
+                    return Err(format!(
+                        "--name must be letters, digits, '-' and '_', not {:?}",
+                        name
+                    ));
+                }
+                options.name = Some(name);
+            }
+            "--input" => options.input = Some(value("--input")?),
+            "--save" => options.save = Some(value("--save")?),
+            "--json" => options.json = true,
+            "--all" => options.all = true,
+            "--jobs" => {
+                let jobs = value("--jobs")?;
+                let jobs = jobs
+                    .parse()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or(format!("--jobs must be a positive number, not {:?}", jobs))?;
+                options.jobs = Some(jobs);
+            }
+            "--timeout" => {
+                let timeout = value("--timeout")?;
+                let timeout = timeout
+                    .parse()
+                    .ok()
+                    .filter(|timeout: &f64| timeout.is_finite() && *timeout > 0.0)
+                    .ok_or(format!(
+                        "--timeout must be a positive number of seconds, not {:?}",
+                        timeout
+                    ))?;
+                options.timeout = Some(Duration::from_secs_f64(timeout));
+            }
+            "--size" => {
+                let size = value("--size")?;
+                let size = size
+                    .parse()
+                    .map_err(|_| format!("--size must be a number, not {:?}", size))?;
+                options.size = Some(size);
+            }
+            "--seed" => {
+                let seed = value("--seed")?;
+                let seed = seed
+                    .parse()
+                    .map_err(|_| format!("--seed must be a number, not {:?}", seed))?;
+                options.seed = Some(seed);
+            }
+            "-h" | "--help" => {
+                options.command = Command::Help;
+                return Ok(options);
+            }
+            _ => return Err(format!("Unexpected argument {:?}", arg)),
+        }
+        first = false;
+    }
+
+    if options.save.is_some() && options.command != Command::Bench {
+        return Err("--save is only for bench".to_string());
+    }
+    if (options.size.is_some() || options.seed.is_some()) && options.command != Command::Generate {
+        return Err("--size and --seed are only for generate".to_string());
+    }
+    if options.json && !matches!(options.command, Command::Run | Command::Check) {
+        return Err("--json is only for run and check".to_string());
+    }
+    if options.all {
+        if options.command != Command::Run {
+            return Err("--all is only for run".to_string());
+        }
+        if options.day.is_some() || options.input.is_some() || options.json {
+            return Err("--all can't be combined with --day, --input or --json".to_string());
+        }
+    } else if options.jobs.is_some() || options.timeout.is_some() {
+        return Err("--jobs and --timeout need --all".to_string());
+    }
+    if options.input.is_some() {
+        if options.day.is_none() {
+            return Err("--input needs --day".to_string());
+        }
+        if options.name.is_some() {
+            return Err("--input can't be combined with --name".to_string());
+        }
+        if matches!(
+            options.command,
+            Command::List | Command::Checksum | Command::Generate
+        ) {
+            return Err("--input is only for run, bench and check".to_string());
+        }
+    }
+
+    Ok(options)
+}
+
+fn input_id(options: &Options, day: u32) -> InputId {
+    InputId::new(day, options.name.as_deref())
+}
+
+/// Whether to run `day`. Without --day, days that don't have the named input
+/// are skipped rather than failed, since few days have any given one.
+fn has_input(store: &InputStore, options: &Options, day: u32) -> bool {
+    options.name.is_none() || options.day.is_some() || store.exists(&input_id(options, day))
+}
+
+fn read_input(store: &InputStore, options: &Options, day: u32) -> Result<String, String> {
+    match options.input.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Couldn't read stdin: {}", e))?;
+            Ok(input)
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))
+        }
+        None => store.read(&input_id(options, day)),
+    }
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("\t{}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A fingerprint of `input`, so results can be matched to the input they
+/// came from. It's the same SHA-256 that `checksums.txt` records.
+fn input_hash(input: &str) -> String {
+    format!("sha256:{}", sha256_hex(input.as_bytes()))
+}
+
+/// Renders `run` as one line of JSON. `expected` is only given for check.
+fn run_json(day: u32, part: u32, run: &Run, expected: Option<Option<&str>>, input: &str) -> String {
+    let mut object = Object::new()
+        .field("day", day)
+        .field("part", part)
+        .field("answer", run.answer.as_ref().ok())
+        .field("type", run.answer_type)
+        .field("parse_ns", run.parse_time.as_nanos())
+        .field("solve_ns", run.solve_time.map(|time| time.as_nanos()))
+        .field(
+            "error",
+            run.answer.as_ref().err().map(|error| error.to_string()),
+        );
+    if let Some(expected) = expected {
+        object = object.field("expected", expected);
+    }
+    object.field("input_hash", input_hash(input)).to_json()
+}
+
+/// Renders a part whose input couldn't be read as one line of JSON, with
+/// the same fields as [`run_json`].
+fn input_error_json(day: u32, part: u32, error: &str, expected: Option<Option<&str>>) -> String {
+    let mut object = Object::new()
+        .field("day", day)
+        .field("part", part)
+        .field("answer", None::<&str>)
+        .field("type", None::<&str>)
+        .field("parse_ns", None::<u128>)
+        .field("solve_ns", None::<u128>)
+        .field("error", error);
+    if let Some(expected) = expected {
+        object = object.field("expected", expected);
+    }
+    object.field("input_hash", None::<&str>).to_json()
+}
+
+/// Prints a generated input for --day, or saves it as a named input and
+/// records its checksum. Without --day, lists the generators.
+fn generate(store: &mut InputStore, options: &Options) -> Result<(), String> {
+    let Some(day) = options.day else {
+        let header = [
+            ("Day", Align::Right),
+            ("Size", Align::Left),
+            ("Default", Align::Right),
+            ("Sizes", Align::Left),
+        ];
+        let rows = GENERATORS
+            .iter()
+            .map(|generator| {
+                [
+                    generator.day.to_string(),
+                    generator.size.to_string(),
+                    generator.default_size.to_string(),
+                    format!("{}-{}", generator.sizes.start(), generator.sizes.end()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        print!("{}", table::render(header, &rows));
+        return Ok(());
+    };
+
+    let generator = gen::find(day).ok_or(format!("No generator for day {}", day))?;
+    let size = options.size.unwrap_or(generator.default_size);
+    let input = generator.generate(options.seed.unwrap_or(0), size)?;
+    if options.name.is_none() {
+        print!("{}", input);
+        return Ok(());
+    }
+
+    let id = input_id(options, day);
+    let path = store.path(&id);
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, input))
+        .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+    store.record(&id)?;
+    store.save()?;
+    println!("Saved to {}", path.display());
+    Ok(())
+}
+
+/// Runs the selected parts of `solution`, returning whether they all
+/// succeeded. `answers` are only given when `input` is from input/2024.
+fn solve(options: &Options, solution: &dyn Puzzle, input: &str, answers: Option<&Answers>) -> bool {
+    let mut ok = true;
+
+    for part in 1..=2 {
+        if options.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        let label = format!("Day {} - Part {}", solution.day(), part);
+        let run = solution.run(input, part);
+        let id = input_id(options, solution.day());
+        let expected = answers.and_then(|answers| answers.get_input(&id, part));
+
+        ok &= match (&run.answer, expected) {
+            (Err(_), _) => false,
+            (Ok(answer), Some(expected)) if options.command == Command::Check => answer == expected,
+            (Ok(_), _) => true,
+        };
+
+        if options.json {
+            let expected = (options.command == Command::Check).then_some(expected);
+            println!("{}", run_json(solution.day(), part, &run, expected, input));
+            continue;
+        }
+
+        match (options.command, &run.answer) {
+            (Command::Run, Ok(answer)) => {
+                println!("{}: {}", label, answer);
+                println!(
+                    "\tparse: {:.2?}, solve: {:.2?}",
+                    run.parse_time,
+                    run.solve_time.unwrap_or_default()
+                );
+            }
+            (_, Ok(answer)) => match expected {
+                Some(expected) if expected == answer => println!("{}: ok", label),
+                Some(expected) => {
+                    println!("{}: WRONG", label);
+                    println!("\tgot {}, expected {}", answer, expected);
+                }
+                None => println!("{}: {} (no known answer)", label, answer),
+            },
+            (_, Err(error)) => {
+                println!("{}: FAILED", label);
+                println!("{}", indent(&error.to_string()));
+            }
+        }
+    }
+
+    ok
+}
+
+/// Benchmarks the selected parts of `solution`, adding a record for each to
+/// `records`, and returns whether they all succeeded.
+fn bench(options: &Options, solution: &dyn Puzzle, input: &str, records: &mut Vec<Record>) -> bool {
+    let config = Config::default();
+    let parse = match solution.bench_parse(&config, input) {
+        Ok(stats) => stats,
+        Err(error) => {
+            println!("Day {} - Parse: FAILED", solution.day());
+            println!("{}", indent(&error.to_string()));
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for part in 1..=2 {
+        if options.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        let label = format!("Day {} - Part {}", solution.day(), part);
+
+        match solution.bench_part(&config, input, part) {
+            Ok(solve) => {
+                println!("{}: {:.2?} over {} runs", label, solve.median, solve.runs);
+                records.push(Record {
+                    day: solution.day(),
+                    part,
+                    parse,
+                    solve,
+                });
+            }
+            Err(error) => {
+                println!("{}: FAILED", label);
+                println!("{}", indent(&error.to_string()));
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// Runs every selected part on a pool of threads and prints one table of
+/// the results, returning whether they all succeeded.
+fn run_all(store: &InputStore, options: &Options) -> bool {
+    let workers = options
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+    let timeout = options.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let parts = (1..=2).filter(|&part| options.part.is_none_or(|p| p == part));
+
+    // Each row is day, part, answer or error, time and status.
+    let mut rows = Vec::new();
+    let mut jobs: Vec<Job<Result<String, String>>> = Vec::new();
+    let mut labels = Vec::new();
+    for &solution in SOLUTIONS {
+        let day = solution.day();
+        if !has_input(store, options, day) {
+            continue;
+        }
+        let input: Arc<str> = match read_input(store, options, day) {
+            Ok(input) => input.into(),
+            Err(message) => {
+                for part in parts.clone() {
+                    rows.push((day, part, message.clone(), None, "error"));
+                }
+                continue;
+            }
+        };
+        for part in parts.clone() {
+            let input = Arc::clone(&input);
+            jobs.push(Box::new(move || {
+                solution.solve(&input, part).map_err(|e| e.to_string())
+            }));
+            labels.push((day, part));
+        }
+    }
+
+    let start = Instant::now();
+    let results = pool::run(jobs, workers, timeout);
+    let elapsed = start.elapsed();
+
+    for ((day, part), finished) in labels.into_iter().zip(results) {
+        let (text, status) = match finished.outcome {
+            Outcome::Done(Ok(answer)) => (answer, "ok"),
+            Outcome::Done(Err(error)) => (error, "error"),
+            Outcome::Panicked(message) => (message, "panicked"),
+            Outcome::TimedOut => (String::new(), "timed out"),
+        };
+        rows.push((day, part, text, Some(finished.elapsed), status));
+    }
+    rows.sort_by_key(|&(day, part, ..)| (day, part));
+
+    let failed = rows.iter().filter(|row| row.4 != "ok").count();
+    let header = [
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        ("Answer", Align::Left),
+        ("Time", Align::Right),
+        ("Status", Align::Left),
+    ];
+    let rows = rows
+        .into_iter()
+        .map(|(day, part, text, time, status)| {
+            [
+                day.to_string(),
+                part.to_string(),
+                text.lines().next().unwrap_or_default().to_string(),
+                time.map_or(String::new(), |time| format!("{:.2?}", time)),
+                status.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print!("{}", table::render(header, &rows));
+    println!(
+        "\n{} parts in {:.2?}, {} at a time, {} failed",
+        rows.len(),
+        elapsed,
+        workers,
+        failed
+    );
+
+    failed == 0
+}
+
+/// The inputs `list` and `checksum` work on: every input of the selected
+/// days, or just the one with the selected name.
+fn selected_inputs(store: &InputStore, options: &Options) -> Result<Vec<InputId>, String> {
+    Ok(store
+        .discover()?
+        .into_iter()
+        .filter(|id| options.day.is_none_or(|day| day == id.day))
+        .filter(|id| options.name.is_none() || id.name == options.name)
+        .collect())
+}
+
+fn list(store: &InputStore, options: &Options) -> Result<(), String> {
+    let ids = selected_inputs(store, options)?;
+    for &solution in SOLUTIONS {
+        let day = solution.day();
+        if options.day.is_some_and(|d| d != day) {
+            continue;
+        }
+        let default = InputId::new(day, None);
+        if options.name.is_none() && !ids.contains(&default) {
+            println!(
+                "Day {:2}: {} (missing)",
+                day,
+                store.path(&default).display()
+            );
+        }
+
+        for id in ids.iter().filter(|id| id.day == day) {
+            let status = match store.verify(id)? {
+                Ok(()) if store.checksum(id).is_none() => "unrecorded",
+                Ok(()) => "ok",
+                Err(Problem::Empty) => "empty",
+                Err(Problem::Crlf) => "CRLF",
+                Err(Problem::Truncated { .. }) => "truncated",
+                Err(Problem::Changed) => "changed",
+            };
+            println!("Day {:2}: {} ({})", day, store.path(id).display(), status);
+        }
+    }
+    Ok(())
+}
+
+/// Records the checksums of the selected inputs, returning whether they were
+/// all recorded.
+fn checksum(store: &mut InputStore, options: &Options) -> Result<bool, String> {
+    let mut ok = true;
+    for id in selected_inputs(store, options)? {
+        match store.record(&id) {
+            Ok(checksum) => println!("{}: {} bytes, sha256 {}", id, checksum.len, checksum.sha256),
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+            }
+        }
+    }
+    store.save()?;
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if options.command == Command::Help {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let mut store = match InputStore::open(INPUT_DIR) {
+        Ok(store) => store,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(name) = &options.name {
+        let solves = !matches!(
+            options.command,
+            Command::List | Command::Checksum | Command::Generate
+        );
+        if solves
+            && !SOLUTIONS
+                .iter()
+                .any(|s| has_input(&store, &options, s.day()))
+        {
+            eprintln!("No day has an input named {:?}", name);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match options.command {
+        Command::List => {
+            return match list(&store, &options) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Command::Checksum => {
+            return match checksum(&mut store, &options) {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::FAILURE,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Command::Generate => {
+            return match generate(&mut store, &options) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Command::Run if options.all => {
+            return if run_all(&store, &options) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            };
+        }
+        _ => {}
+    }
+
+    // There are no known answers for inputs from outside input/2024.
+    let answers = match options.command {
+        Command::Check if options.input.is_none() => match Answers::load(ANSWERS_PATH) {
+            Ok(answers) => Some(answers),
+            Err(message) => {
+                eprintln!("{}", message);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => None,
+    };
+
+    let mut ok = true;
+    let mut records = Vec::new();
+    for &solution in SOLUTIONS {
+        if options.day.is_some_and(|day| day != solution.day())
+            || !has_input(&store, &options, solution.day())
+        {
+            continue;
+        }
+        match read_input(&store, &options, solution.day()) {
+            Ok(input) if options.command == Command::Bench => {
+                ok &= bench(&options, solution, &input, &mut records)
+            }
+            Ok(input) => ok &= solve(&options, solution, &input, answers.as_ref()),
+            // Every selected part still gets a record, so nothing goes
+            // missing from the output.
+            Err(message) if options.json => {
+                for part in (1..=2).filter(|&part| options.part.is_none_or(|p| p == part)) {
+                    let expected = (options.command == Command::Check).then(|| {
+                        let id = input_id(&options, solution.day());
+                        answers
+                            .as_ref()
+                            .and_then(|answers| answers.get_input(&id, part))
+                    });
+                    let json = input_error_json(solution.day(), part, &message, expected);
+                    println!("{}", json);
+                }
+                ok = false;
+            }
+            Err(message) => {
+                eprintln!("Day {}: {}", solution.day(), message);
+                ok = false;
+            }
+        }
+    }
+
+    if options.command == Command::Bench && !records.is_empty() {
+        println!();
+        print!("{}", bench::table(&records));
+        let path = options.save.as_deref().unwrap_or(BENCH_PATH);
+        let saved = PathBuf::from(path)
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, bench::to_json(&records)));
+        match saved {
+            Ok(()) => println!("Saved to {}", path),
+            Err(e) => {
+                eprintln!("Couldn't write {}: {}", path, e);
+                ok = false;
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> impl Iterator<Item = String> + '_ {
+        args.split_whitespace().map(str::to_string)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args("")).unwrap();
+        assert_eq!(options.command, Command::Run);
+        assert_eq!((options.day, options.part), (None, None));
+
+        let options = parse_args(args("bench --day 5 --part 2 --input - --save b.json")).unwrap();
+        assert_eq!(options.command, Command::Bench);
+        assert_eq!((options.day, options.part), (Some(5), Some(2)));
+        assert_eq!(options.input.as_deref(), Some("-"));
+        assert_eq!(options.save.as_deref(), Some("b.json"));
+
+        let options = parse_args(args("--day 3")).unwrap();
+        assert_eq!(options.command, Command::Run);
+        assert!(!options.json);
+
+        let options = parse_args(args("check --json")).unwrap();
+        assert_eq!(options.command, Command::Check);
+        assert!(options.json);
+
+        let options = parse_args(args("check --day 6 --name alt")).unwrap();
+        assert_eq!(options.name.as_deref(), Some("alt"));
+
+        let options = parse_args(args("checksum")).unwrap();
+        assert_eq!(options.command, Command::Checksum);
+
+        let options = parse_args(args("generate --day 6 --size 20 --seed 3 --name big")).unwrap();
+        assert_eq!(options.command, Command::Generate);
+        assert_eq!((options.size, options.seed), (Some(20), Some(3)));
+
+        let options = parse_args(args("run --all --jobs 4 --timeout 0.5")).unwrap();
+        assert!(options.all);
+        assert_eq!(options.jobs, Some(4));
+        assert_eq!(options.timeout, Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(
+            input_hash("abc"),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_run_json() {
+        let run = SOLUTIONS[0].run("3   4\n4   3\n", 2);
+        let json = run_json(1, 2, &run, Some(None), "3   4\n4   3\n");
+        assert!(
+            json.starts_with(r#"{"day": 1, "part": 2, "answer": "7", "type": "i32", "parse_ns": "#)
+        );
+        assert!(json.contains(r#""error": null, "expected": null, "input_hash": "sha256:"#));
+
+        let run = SOLUTIONS[0].run("3   x\n", 1);
+        let json = run_json(1, 1, &run, None, "3   x\n");
+        assert!(json.contains(r#""answer": null"#));
+        assert!(json.contains(r#""solve_ns": null, "error": "Invalid input for day 1"#));
+        assert!(!json.contains(r#""expected":"#));
+
+        let json = input_error_json(6, 2, "day6.txt is empty", Some(Some("1")));
+        assert_eq!(
+            json,
+            r#"{"day": 6, "part": 2, "answer": null, "type": null, "parse_ns": null, "solve_ns": null, "error": "day6.txt is empty", "expected": "1", "input_hash": null}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(args("--day 26")).is_err());
+        assert!(parse_args(args("--day")).is_err());
+        assert!(parse_args(args("--part 3")).is_err());
+        assert!(parse_args(args("--input day1.txt")).is_err());
+        assert!(parse_args(args("--day 1 list")).is_err());
+        assert!(parse_args(args("frobnicate")).is_err());
+        assert!(parse_args(args("run --save b.json")).is_err());
+        assert!(parse_args(args("bench --json")).is_err());
+        assert!(parse_args(args("check --all")).is_err());
+        assert!(parse_args(args("--all --day 1")).is_err());
+        assert!(parse_args(args("--jobs 2")).is_err());
+        assert!(parse_args(args("--all --jobs 0")).is_err());
+        assert!(parse_args(args("--all --timeout -1")).is_err());
+        assert!(parse_args(args("--name a.b")).is_err());
+        assert!(parse_args(args("--day 1 --name alt --input day1.txt")).is_err());
+        assert!(parse_args(args("checksum --day 1 --input day1.txt")).is_err());
+        assert!(parse_args(args("run --day 1 --size 10")).is_err());
+        assert!(parse_args(args("generate --day 1 --seed -1")).is_err());
+        assert!(parse_args(args("generate --day 1 --input day1.txt")).is_err());
+
+        let options = parse_args(args("--day 1 --help --bogus")).unwrap();
+        assert_eq!(options.command, Command::Help);
+    }
+}
@@ -0,0 +1,283 @@
+//! Small helpers for the line- and section-based formats AoC inputs use.
+//!
+//! Every helper takes the whole puzzle `input` alongside the `text` being
+//! parsed, which must be a slice of `input`, so errors can point at the
+//! offending line and column.
+
+use std::{any::type_name, ops::Range, str::FromStr};
+
+use crate::errors::ParseInputError;
+
+/// Parses `token`, pointing the error at it on failure.
+pub fn token<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseInputError> {
+    token
+        .parse()
+        .map_err(|_| ParseInputError::new(expected).at_str(input, token))
+}
+
+/// Like `str::split_once`, pointing the error at `text` when `delimiter` is
+/// missing.
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseInputError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseInputError::new(format!("{:?}", delimiter)).at_str(input, text))
+}
+
+/// Splits `text` into blocks separated by blank lines. Lines holding only
+/// whitespace count as blank, so `\r\n` inputs split the same way. Sections
+/// don't include their trailing line break.
+pub fn sections(text: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&text[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        sections.push(&text[start..end]);
+    }
+
+    sections
+}
+
+/// Like `sections`, but requires exactly `N` of them.
+pub fn sections_n<'a, const N: usize>(
+    input: &str,
+    text: &'a str,
+) -> Result<[&'a str; N], ParseInputError> {
+    let sections = sections(text);
+    let found = sections.len();
+    sections.try_into().map_err(|_| {
+        ParseInputError::new(format!("{} sections separated by blank lines", N))
+            .with_found(found.to_string())
+            .at_str(input, text)
+    })
+}
+
+/// Parses every item of a `separator`-delimited list. Items are trimmed, and a
+/// blank `separator` splits on any run of whitespace.
+pub fn list<T: FromStr>(
+    input: &str,
+    text: &str,
+    separator: &str,
+    expected: &str,
+) -> Result<Vec<T>, ParseInputError> {
+    if separator.trim().is_empty() {
+        text.split_whitespace()
+            .map(|item| token(input, item, expected))
+            .collect()
+    } else {
+        text.split(separator)
+            .map(|item| token(input, item.trim(), expected))
+            .collect()
+    }
+}
+
+/// Parses two items around `separator`, such as the `3,4` in `p=3,4`.
+pub fn pair<T: FromStr>(
+    input: &str,
+    text: &str,
+    separator: &str,
+    expected: &str,
+) -> Result<(T, T), ParseInputError> {
+    let (a, b) = split_once(input, text, separator)?;
+    Ok((
+        token(input, a.trim(), expected)?,
+        token(input, b.trim(), expected)?,
+    ))
+}
+
+/// Splits a `key: value` line, trimming both sides.
+pub fn key_value<'a>(input: &str, line: &'a str) -> Result<(&'a str, &'a str), ParseInputError> {
+    let (key, value) = split_once(input, line, ":")?;
+    Ok((key.trim(), value.trim()))
+}
+
+/// Returns the value of a `key: value` line whose key must be `key`.
+pub fn field<'a>(input: &str, line: &'a str, key: &str) -> Result<&'a str, ParseInputError> {
+    line.strip_prefix(key)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .map(str::trim)
+        .ok_or_else(|| {
+            ParseInputError::new(format!("{:?}", format!("{}: ", key))).at_str(input, line)
+        })
+}
+
+/// Strips `prefix` from `text`, such as the `p=` in `p=3,4`.
+pub fn prefixed<'a>(input: &str, text: &'a str, prefix: &str) -> Result<&'a str, ParseInputError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseInputError::new(format!("{:?}", prefix)).at_str(input, text))
+}
+
+/// Every integer in `text`, in order, with its byte range in `text`.
+/// Whatever surrounds the numbers is ignored, except that a `+` or `-` directly
+/// before the digits is taken as the sign, unless it follows another digit
+/// (as in a `1-5` range). Numbers that don't fit `T`, including negative ones
+/// for unsigned `T`, are an error rather than being skipped.
+pub fn int_spans<T: FromStr>(
+    input: &str,
+    text: &str,
+) -> Result<Vec<(T, Range<usize>)>, ParseInputError> {
+    let bytes = text.as_bytes();
+    let expected = format!("an integer that fits in {}", type_name::<T>());
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let signed = i > 0
+            && matches!(bytes[i - 1], b'+' | b'-')
+            && (i < 2 || !bytes[i - 2].is_ascii_digit());
+        let start = if signed { i - 1 } else { i };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        result.push((token(input, &text[start..i], &expected)?, start..i));
+    }
+
+    Ok(result)
+}
+
+/// Every integer in `text`, in order. See `int_spans` for the rules.
+pub fn ints<T: FromStr>(input: &str, text: &str) -> Result<Vec<T>, ParseInputError> {
+    Ok(int_spans(input, text)?
+        .into_iter()
+        .map(|(value, _)| value)
+        .collect())
+}
+
+/// Like `ints`, but requires exactly `N` integers.
+pub fn ints_n<T: FromStr, const N: usize>(
+    input: &str,
+    text: &str,
+) -> Result<[T; N], ParseInputError> {
+    let ints = ints(input, text)?;
+    let found = ints.len();
+    ints.try_into().map_err(|_| {
+        ParseInputError::new(format!("{} integers", N))
+            .with_found(found.to_string())
+            .at_str(input, text)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(sections("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(sections("a\r\nb\r\n\r\n\r\nc"), vec!["a\r\nb", "c"]);
+        assert_eq!(sections("\n\na\n  \nb\n\n"), vec!["a", "b"]);
+        assert!(sections("").is_empty());
+    }
+
+    #[test]
+    fn test_sections_n() {
+        let input = "1|2\n\n1,2\n";
+        assert_eq!(sections_n::<2>(input, input).unwrap(), ["1|2", "1,2"]);
+
+        let err = sections_n::<3>(input, input).unwrap_err();
+        assert_eq!(err.expected, "3 sections separated by blank lines");
+        assert_eq!(err.found.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_list() {
+        let input = "1, 2,3\n4  5\t6\n";
+        let (a, b) = input.split_once('\n').unwrap();
+        assert_eq!(list::<u8>(input, a, ",", "a number").unwrap(), [1, 2, 3]);
+        assert_eq!(list::<u8>(input, b, " ", "a number").unwrap(), [4, 5, 6]);
+
+        let input = "1\n2,x,3";
+        let err = list::<u8>(input, &input[2..], ",", "a number").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        assert_eq!(err.expected, "a number");
+    }
+
+    #[test]
+    fn test_pair() {
+        let input = "p=3,-4";
+        assert_eq!(pair::<i32>(input, &input[2..], ",", "").unwrap(), (3, -4));
+
+        let err = pair::<i32>(input, &input[2..], "|", "").unwrap_err();
+        assert_eq!(err.expected, "\"|\"");
+        assert_eq!(err.column, Some(3));
+    }
+
+    #[test]
+    fn test_key_value() {
+        let input = "Register A: 729\nProgram: 0,1";
+        let (a, b) = input.split_once('\n').unwrap();
+        assert_eq!(key_value(input, a).unwrap(), ("Register A", "729"));
+        assert_eq!(field(input, b, "Program").unwrap(), "0,1");
+
+        let err = field(input, b, "Register B").unwrap_err();
+        assert_eq!(err.expected, "\"Register B: \"");
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn test_prefixed() {
+        let input = "p=1,2 v=3,4";
+        assert_eq!(prefixed(input, &input[6..], "v=").unwrap(), "3,4");
+        assert_eq!(prefixed(input, input, "v=").unwrap_err().column, Some(1));
+    }
+
+    #[test]
+    fn test_ints() {
+        let input = "Button A: X+94, Y-34\np=-1,2 v=3,-40";
+        assert_eq!(ints::<i64>(input, input).unwrap(), [94, -34, -1, 2, 3, -40]);
+        assert_eq!(ints::<u8>(input, "a-b--c").unwrap(), []);
+        assert_eq!(ints::<u32>(input, "1-5,7-10").unwrap(), [1, 5, 7, 10]);
+        assert_eq!(ints::<i32>(input, "--3 +-4").unwrap(), [-3, -4]);
+    }
+
+    #[test]
+    fn test_int_spans() {
+        let input = "x=-12, y=+7";
+        assert_eq!(
+            int_spans::<i8>(input, input).unwrap(),
+            [(-12, 2..5), (7, 9..11)]
+        );
+    }
+
+    #[test]
+    fn test_ints_errors() {
+        let input = "x=99999999999999999999";
+        let err = ints::<i64>(input, input).unwrap_err();
+        assert_eq!(err.column, Some(3));
+        assert_eq!(err.expected, "an integer that fits in i64");
+
+        let input = "a=1\nb=-2";
+        let err = ints::<u32>(input, input).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(3)));
+        assert_eq!(err.found.as_deref(), Some("\"-2\""));
+    }
+
+    #[test]
+    fn test_ints_n() {
+        let input = "p=0,4 v=3,-3";
+        assert_eq!(ints_n::<i64, 4>(input, input).unwrap(), [0, 4, 3, -3]);
+
+        let err = ints_n::<i64, 2>(input, input).unwrap_err();
+        assert_eq!(err.expected, "2 integers");
+        assert_eq!(err.found.as_deref(), Some("4"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::direction::Direction;
+
+/// A grid position or offset. `x` is the row and `y` the column, matching
+/// [`crate::vec2d::Vec2D::get`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts each coordinate, returning `None` if either doesn't fit.
+    pub fn try_cast<U>(self) -> Option<Point<U>>
+    where
+        U: TryFrom<T>,
+    {
+        Some(Point::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+
+    pub fn cast<U>(self) -> Point<U>
+    where
+        U: From<T>,
+    {
+        Point::new(U::from(self.x), U::from(self.y))
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn abs_diff(a: T, b: T) -> T {
+        if a > b {
+            a - b
+        } else {
+            b - a
+        }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        Self::abs_diff(self.x, other.x) + Self::abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        Self::abs_diff(self.x, other.x).max(Self::abs_diff(self.y, other.y))
+    }
+}
+
+impl Point<usize> {
+    /// Steps one cell in `direction`, or `None` when that leaves the
+    /// non-negative quadrant.
+    pub fn checked_add(self, direction: Direction) -> Option<Self> {
+        direction.checked_add(self.into()).map(Self::from)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+macro_rules! impl_signed_direction {
+    ($($t:ty),*) => {
+        $(
+            impl From<Direction> for Point<$t> {
+                fn from(direction: Direction) -> Self {
+                    let (x, y): (isize, isize) = direction.into();
+                    Self::new(x as $t, y as $t)
+                }
+            }
+
+            impl TryFrom<Point<$t>> for Direction {
+                type Error = Point<$t>;
+
+                /// Succeeds for the eight unit offsets around the origin.
+                fn try_from(point: Point<$t>) -> Result<Self, Self::Error> {
+                    match (point.x, point.y) {
+                        (-1, 0) => Ok(Direction::Up),
+                        (1, 0) => Ok(Direction::Down),
+                        (0, -1) => Ok(Direction::Left),
+                        (0, 1) => Ok(Direction::Right),
+                        (-1, -1) => Ok(Direction::UpLeft),
+                        (-1, 1) => Ok(Direction::UpRight),
+                        (1, -1) => Ok(Direction::DownLeft),
+                        (1, 1) => Ok(Direction::DownRight),
+                        _ => Err(point),
+                    }
+                }
+            }
+
+            impl Add<Direction> for Point<$t> {
+                type Output = Self;
+
+                fn add(self, direction: Direction) -> Self::Output {
+                    self + Self::from(direction)
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_direction!(i32, i64, isize);
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self::Output {
+        Self::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3i64, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(a + b, Point::new(2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::new(5usize, 1).manhattan(Point::new(2, 3)), 5);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            Point::new(2i64, 3).try_cast::<usize>(),
+            Some(Point::new(2, 3))
+        );
+        assert_eq!(Point::new(-1i64, 3).try_cast::<usize>(), None);
+        assert_eq!(Point::new(2u8, 3).cast::<i64>(), Point::new(2, 3));
+        assert_eq!(<(usize, usize)>::from(Point::new(1, 2)), (1, 2));
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Point::<i64>::from(Direction::UpRight), Point::new(-1, 1));
+        assert_eq!(
+            Direction::try_from(Point::new(0i32, -1)),
+            Ok(Direction::Left)
+        );
+        assert!(Direction::try_from(Point::new(0isize, 0)).is_err());
+        assert_eq!(Point::new(2i32, 2) + Direction::Down, Point::new(3, 2));
+        assert_eq!(Point::new(0usize, 1).checked_add(Direction::Up), None);
+        assert_eq!(
+            Point::new(0usize, 1).checked_add(Direction::Left),
+            Some(Point::new(0, 0))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! A small std-only worker pool that runs jobs with a per-job timeout,
+//! catching panics.
+//!
+//! Threads can't be killed, so a job that times out is abandoned: its worker
+//! is replaced and whatever it eventually returns is ignored.
+
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+pub type Job<T> = Box<dyn FnOnce() -> T + Send>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Done(T),
+    /// The job panicked, with the panic's message.
+    Panicked(String),
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct Finished<T> {
+    pub outcome: Outcome<T>,
+    /// How long the job ran for, or the timeout if it timed out.
+    pub elapsed: Duration,
+}
+
+type Queue<T> = Arc<Mutex<VecDeque<(usize, Job<T>)>>>;
+
+enum Message<T> {
+    Started(usize, Instant),
+    Finished(usize, Outcome<T>, Duration),
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn worker<T>(queue: Queue<T>, sender: Sender<Message<T>>) {
+    loop {
+        let Some((index, job)) = queue.lock().unwrap().pop_front() else {
+            return;
+        };
+
+        let start = Instant::now();
+        if sender.send(Message::Started(index, start)).is_err() {
+            return;
+        }
+        let outcome = match panic::catch_unwind(AssertUnwindSafe(job)) {
+            Ok(value) => Outcome::Done(value),
+            Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+        };
+        if sender
+            .send(Message::Finished(index, outcome, start.elapsed()))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Runs `jobs` on up to `workers` threads, returning their results in the
+/// same order as `jobs`.
+pub fn run<T: Send + 'static>(
+    jobs: Vec<Job<T>>,
+    workers: usize,
+    timeout: Duration,
+) -> Vec<Finished<T>> {
+    let total = jobs.len();
+    let queue: Queue<T> = Arc::new(Mutex::new(jobs.into_iter().enumerate().collect()));
+    let (sender, receiver) = mpsc::channel();
+    let spawn_worker = || {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        thread::spawn(move || worker(queue, sender));
+    };
+    for _ in 0..workers.clamp(1, total.max(1)) {
+        spawn_worker();
+    }
+
+    let mut results = (0..total).map(|_| None).collect::<Vec<_>>();
+    let mut running = HashMap::new();
+    let mut remaining = total;
+    while remaining > 0 {
+        let wait = running
+            .values()
+            .map(|&start: &Instant| (start + timeout).saturating_duration_since(Instant::now()))
+            .min()
+            .unwrap_or(timeout);
+
+        match receiver.recv_timeout(wait) {
+            Ok(Message::Started(index, start)) => {
+                running.insert(index, start);
+            }
+            Ok(Message::Finished(index, outcome, elapsed)) => {
+                // Jobs that already timed out are no longer running.
+                if running.remove(&index).is_some() {
+                    results[index] = Some(Finished { outcome, elapsed });
+                    remaining -= 1;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                let expired = running
+                    .iter()
+                    .filter(|(_, &start)| now.duration_since(start) >= timeout)
+                    .map(|(&index, _)| index)
+                    .collect::<Vec<_>>();
+                for index in expired {
+                    running.remove(&index);
+                    results[index] = Some(Finished {
+                        outcome: Outcome::TimedOut,
+                        elapsed: timeout,
+                    });
+                    remaining -= 1;
+                    // The worker is stuck on the abandoned job.
+                    spawn_worker();
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the pool holds a sender"),
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every job finished or timed out"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes<T>(finished: Vec<Finished<T>>) -> Vec<Outcome<T>> {
+        finished.into_iter().map(|f| f.outcome).collect()
+    }
+
+    #[test]
+    fn test_run() {
+        let jobs = (0..20)
+            .map(|i| Box::new(move || i * i) as Job<_>)
+            .collect::<Vec<_>>();
+        let results = outcomes(run(jobs, 4, Duration::from_secs(10)));
+        assert_eq!(
+            results,
+            (0..20).map(|i| Outcome::Done(i * i)).collect::<Vec<_>>()
+        );
+
+        assert!(run::<()>(Vec::new(), 4, Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn test_panics_and_timeouts() {
+        let jobs: Vec<Job<u32>> = vec![
+            Box::new(|| panic!("boom {}", 1)),
+            Box::new(|| {
+                thread::sleep(Duration::from_secs(5));
+                2
+            }),
+            Box::new(|| 3),
+            Box::new(|| 4),
+        ];
+        let results = outcomes(run(jobs, 1, Duration::from_millis(100)));
+        assert_eq!(
+            results,
+            vec![
+                Outcome::Panicked("boom 1".to_string()),
+                Outcome::TimedOut,
+                Outcome::Done(3),
+                Outcome::Done(4),
+            ]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! The [`Solution`] trait every day implements, and a registry of them so
+//! runners can call them without going through cargo-aoc.
+
+use std::{
+    any,
+    error::Error,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bench::{self, Config, Stats},
+    errors::ParseInputError,
+};
+
+/// A day's puzzle, split into parsing the input once and solving each part
+/// from the parsed value.
+pub trait Solution {
+    const DAY: u32;
+
+    /// The parsed input, which may borrow from the input text.
+    type Parsed<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseInputError>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Self::Answer1, Box<dyn Error>>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Self::Answer2, Box<dyn Error>>;
+
+    /// [`Solution::parse`], with any error tagged with the day.
+    fn parse_input(input: &str) -> Result<Self::Parsed<'_>, ParseInputError> {
+        Self::parse(input).map_err(|e| e.with_day(Self::DAY))
+    }
+
+    fn solve_part1(input: &str) -> Result<Self::Answer1, Box<dyn Error>> {
+        Self::part1(&Self::parse_input(input)?)
+    }
+
+    fn solve_part2(input: &str) -> Result<Self::Answer2, Box<dyn Error>> {
+        Self::part2(&Self::parse_input(input)?)
+    }
+}
+
+/// The outcome of parsing an input and solving one part from it.
+pub struct Run {
+    /// The answer rendered as text.
+    pub answer: Result<String, Box<dyn Error>>,
+    /// The answer's type, e.g. `i32`.
+    pub answer_type: &'static str,
+    pub parse_time: Duration,
+    /// `None` if parsing failed.
+    pub solve_time: Option<Duration>,
+}
+
+/// A [`Solution`] with its types erased, so every day can share a registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u32;
+
+    /// Parses `input` and solves the part numbered `part`, counting from 1,
+    /// timing each step.
+    fn run(&self, input: &str, part: u32) -> Run;
+
+    /// [`Puzzle::run`], keeping just the answer.
+    fn solve(&self, input: &str, part: u32) -> Result<String, Box<dyn Error>> {
+        self.run(input, part).answer
+    }
+
+    /// Times parsing `input` on its own.
+    fn bench_parse(&self, config: &Config, input: &str) -> Result<Stats, ParseInputError>;
+
+    /// Parses `input` once, then times solving `part` from it.
+    fn bench_part(&self, config: &Config, input: &str, part: u32) -> Result<Stats, Box<dyn Error>>;
+}
+
+fn no_part(part: u32) -> Box<dyn Error> {
+    format!("There is no part {}", part).into()
+}
+
+/// The last segment of `T`'s path, so `String` rather than
+/// `alloc::string::String`.
+fn short_type_name<T>() -> &'static str {
+    let name = any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn timed_run<S: Solution, T: Display>(
+    input: &str,
+    solve: impl for<'a> Fn(&S::Parsed<'a>) -> Result<T, Box<dyn Error>>,
+) -> Run {
+    let start = Instant::now();
+    let parsed = S::parse_input(input);
+    let parse_time = start.elapsed();
+
+    let (answer, solve_time) = match parsed {
+        Ok(parsed) => {
+            let start = Instant::now();
+            let answer = solve(&parsed).map(|answer| answer.to_string());
+            (answer, Some(start.elapsed()))
+        }
+        Err(error) => (Err(error.into()), None),
+    };
+
+    Run {
+        answer,
+        answer_type: short_type_name::<T>(),
+        parse_time,
+        solve_time,
+    }
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, part: u32) -> Run {
+        match part {
+            1 => timed_run::<S, _>(input, S::part1),
+            2 => timed_run::<S, _>(input, S::part2),
+            _ => Run {
+                answer: Err(no_part(part)),
+                answer_type: "",
+                parse_time: Duration::ZERO,
+                solve_time: None,
+            },
+        }
+    }
+
+    fn bench_parse(&self, config: &Config, input: &str) -> Result<Stats, ParseInputError> {
+        bench::measure(config, || S::parse_input(input))
+    }
+
+    fn bench_part(&self, config: &Config, input: &str, part: u32) -> Result<Stats, Box<dyn Error>> {
+        let parsed = S::parse_input(input)?;
+        match part {
+            1 => bench::measure(config, || S::part1(&parsed)),
+            2 => bench::measure(config, || S::part2(&parsed)),
+            _ => Err(no_part(part)),
+        }
+    }
+}
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &crate::day1::Day1,
+    &crate::day2::Day2,
+    &crate::day3::Day3,
+    &crate::day4::Day4,
+    &crate::day5::Day5,
+    &crate::day6::Day6,
+    &crate::day7::Day7,
+    &crate::day8::Day8,
+    &crate::day9::Day9,
+    &crate::day10::Day10,
+    &crate::day11::Day11,
+    &crate::day12::Day12,
+    &crate::day13::Day13,
+    &crate::day14::Day14,
+    &crate::day15::Day15,
+    &crate::day16::Day16,
+    &crate::day17::Day17,
+    &crate::day18::Day18,
+    &crate::day19::Day19,
+    &crate::day20::Day20,
+];
+
+pub fn find(day: u32) -> Option<&'static dyn Puzzle> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert!(SOLUTIONS
+            .iter()
+            .enumerate()
+            .all(|(i, s)| s.day() == i as u32 + 1));
+        assert_eq!(find(20).map(|s| s.day()), Some(20));
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = find(1).unwrap();
+        assert_eq!(solution.solve("3   4\n4   3\n", 1).unwrap(), "0");
+        assert_eq!(solution.solve("3   4\n4   3\n", 2).unwrap(), "7");
+        assert!(solution.solve("3   4\n4   3\n", 0).is_err());
+        assert!(solution.solve("3   4\n4   3\n", 3).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let solution = find(17).unwrap();
+        let run = solution.run(
+            "Register A: 10\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4",
+            1,
+        );
+        assert_eq!(run.answer.unwrap(), "2");
+        assert_eq!(run.answer_type, "String");
+        assert!(run.solve_time.is_some());
+
+        let run = find(1).unwrap().run("3   4\n4\n", 2);
+        assert!(run.answer.is_err());
+        assert_eq!(run.answer_type, "i32");
+        assert!(run.solve_time.is_none());
+    }
+
+    #[test]
+    fn test_bench() {
+        let config = Config {
+            warmup: Duration::ZERO,
+            budget: Duration::ZERO,
+            min_runs: 2,
+            max_runs: 2,
+        };
+        let solution = find(1).unwrap();
+        assert_eq!(
+            solution
+                .bench_parse(&config, "3   4\n4   3\n")
+                .unwrap()
+                .runs,
+            2
+        );
+        assert_eq!(
+            solution
+                .bench_part(&config, "3   4\n4   3\n", 2)
+                .unwrap()
+                .runs,
+            2
+        );
+
+        let err = solution.bench_parse(&config, "3   4\n4\n").unwrap_err();
+        assert_eq!(err.day, Some(1));
+        assert!(solution.bench_part(&config, "3   4\n4\n", 1).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use crate::{direction::Direction, vec2d::Vec2D};
+
+/// An unbounded grid that only stores the cells that were set. Positions are
+/// signed so the grid can grow in every direction.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SparseGrid<T> {
+    pub data: HashMap<(i64, i64), T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            data: HashMap::new(),
+        }
+    }
+
+    /// Copies the cells of `grid` for which `keep` returns true.
+    pub fn from_vec2d<F>(grid: &Vec2D<T>, mut keep: F) -> Self
+    where
+        T: Clone,
+        F: FnMut(&T) -> bool,
+    {
+        Self {
+            data: grid
+                .iter_positions()
+                .filter(|(_, value)| keep(value))
+                .map(|((x, y), value)| ((x as i64, y as i64), value.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.data.get(&(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        self.data.get_mut(&(x, y))
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, x: i64, y: i64, default: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.data.entry((x, y)).or_insert_with(default)
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.data.contains_key(&(x, y))
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, value: T) {
+        self.data.insert((x, y), value);
+    }
+
+    pub fn remove(&mut self, x: i64, y: i64) -> Option<T> {
+        self.data.remove(&(x, y))
+    }
+
+    /// Yields every stored cell with its position, in no particular order.
+    pub fn iter_positions(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.data.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    /// Returns the first matching position in row-major order.
+    pub fn find_first(&self, value: T) -> Option<(i64, i64)>
+    where
+        T: PartialEq,
+    {
+        self.iter_positions()
+            .filter(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+            .min()
+    }
+
+    /// Returns every matching position in row-major order.
+    pub fn find_all(&self, value: T) -> Vec<(i64, i64)>
+    where
+        T: PartialEq,
+    {
+        let mut result = self
+            .iter_positions()
+            .filter(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+
+    /// Yields the stored orthogonal neighbours of `pos`.
+    pub fn neighbors4(&self, pos: (i64, i64)) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.neighbors(pos, &Direction::CARDINAL)
+    }
+
+    /// Yields the stored orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbors8(&self, pos: (i64, i64)) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.neighbors(pos, &Direction::ALL)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: (i64, i64),
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = ((i64, i64), &'a T)> {
+        directions.iter().filter_map(move |dir| {
+            let (dx, dy): (isize, isize) = (*dir).into();
+            let next = (pos.0 + dx as i64, pos.1 + dy as i64);
+            self.get(next.0, next.1).map(|value| (next, value))
+        })
+    }
+
+    /// Returns the inclusive `(min, max)` corners of the stored cells.
+    pub fn bounding_box(&self) -> Option<((i64, i64), (i64, i64))> {
+        let mut positions = self.data.keys();
+        let first = *positions.next()?;
+
+        Some(positions.fold((first, first), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+
+    /// Builds a dense grid covering the bounding box, filling unset cells
+    /// with `default`. Also returns the position of the dense grid's `(0, 0)`.
+    pub fn to_vec2d(&self, default: T) -> (Vec2D<T>, (i64, i64))
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounding_box() else {
+            return (Vec2D::new_default((0, 0), default), (0, 0));
+        };
+
+        let size = ((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize);
+        let grid = Vec2D::from_fn(size, |(x, y)| {
+            self.get(min.0 + x as i64, min.1 + y as i64)
+                .unwrap_or(&default)
+                .clone()
+        });
+        (grid, min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = SparseGrid::new();
+        grid.set(-3, 2, 'a');
+        grid.set(1, -1, 'b');
+        *grid.get_or_insert_with(0, 0, || 'c') = 'd';
+
+        assert_eq!(grid.get(-3, 2), Some(&'a'));
+        assert_eq!(grid.get(0, 0), Some(&'d'));
+        assert_eq!(grid.get(5, 5), None);
+        assert_eq!(grid.find_first('b'), Some((1, -1)));
+        assert_eq!(grid.remove(1, -1), Some('b'));
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut grid = SparseGrid::new();
+        grid.set(-1, 0, 1);
+        grid.set(1, 1, 2);
+        grid.set(5, 5, 3);
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![((-1, 0), &1)]
+        );
+        assert_eq!(grid.neighbors8((0, 0)).count(), 2);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let dense = Vec2D::new_chars("..#\n#..\n...");
+        let grid = SparseGrid::from_vec2d(&dense, |c| *c == '#');
+
+        assert_eq!(grid.find_all('#'), vec![(0, 2), (1, 0)]);
+        assert_eq!(grid.bounding_box(), Some(((0, 0), (1, 2))));
+
+        let (back, origin) = grid.to_vec2d('.');
+        assert_eq!(origin, (0, 0));
+        assert_eq!(back.to_string(), "..#\n#..\n");
+    }
+}
@@ -0,0 +1,55 @@
+//! Plain-text tables for the runner's reports.
+
+use std::fmt::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Renders `rows` under `header`, padding every column to its widest cell.
+/// Trailing spaces are trimmed from each line.
+pub fn render<const N: usize>(header: [(&str, Align); N], rows: &[[String; N]]) -> String {
+    let mut widths = header.map(|(title, _)| title.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut line = |cells: [&str; N]| {
+        let cells = cells
+            .iter()
+            .zip(header.iter().zip(widths))
+            .map(|(cell, ((_, align), width))| match align {
+                Align::Left => format!("{:<width$}", cell),
+                Align::Right => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+    };
+    line(header.map(|(title, _)| title));
+    for row in rows {
+        line(row.each_ref().map(String::as_str));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rows = [
+            ["1".to_string(), "ok".to_string()],
+            ["20".to_string(), "timed out".to_string()],
+        ];
+        assert_eq!(
+            render([("Day", Align::Right), ("Status", Align::Left)], &rows),
+            "Day  Status\n  1  ok\n 20  timed out\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::{Index, IndexMut};
+
+use crate::vec2d::{Rect, Vec2D, Vec2DView};
+
+/// A grid whose edges wrap around, so any signed position maps to a cell.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TorusGrid<T> {
+    pub grid: Vec2D<T>,
+}
+
+impl<T> TorusGrid<T> {
+    pub fn new_default(size: (usize, usize), default: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            grid: Vec2D::new_default(size, default),
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.grid.size
+    }
+
+    /// Maps a signed position onto the grid, wrapping modulo its size.
+    pub fn wrap(&self, (x, y): (i64, i64)) -> (usize, usize) {
+        (
+            x.rem_euclid(self.grid.size.0 as i64) as usize,
+            y.rem_euclid(self.grid.size.1 as i64) as usize,
+        )
+    }
+
+    /// Moves `steps` times by `offset` from `pos`, wrapping around the edges.
+    pub fn offset(&self, pos: (usize, usize), offset: (i64, i64), steps: i64) -> (usize, usize) {
+        // Reduce first so large step counts can't overflow.
+        let dx = offset.0.rem_euclid(self.grid.size.0 as i64)
+            * steps.rem_euclid(self.grid.size.0 as i64);
+        let dy = offset.1.rem_euclid(self.grid.size.1 as i64)
+            * steps.rem_euclid(self.grid.size.1 as i64);
+        self.wrap((pos.0 as i64 + dx, pos.1 as i64 + dy))
+    }
+
+    pub fn get(&self, pos: (i64, i64)) -> &T {
+        &self.grid[self.wrap(pos)]
+    }
+
+    pub fn get_mut(&mut self, pos: (i64, i64)) -> &mut T {
+        let pos = self.wrap(pos);
+        &mut self.grid[pos]
+    }
+
+    pub fn set(&mut self, pos: (i64, i64), value: T) {
+        *self.get_mut(pos) = value;
+    }
+
+    /// Returns the top-left, top-right, bottom-left and bottom-right
+    /// quadrants. For odd sizes the middle row or column belongs to none.
+    pub fn quadrants(&self) -> [Vec2DView<'_, T>; 4] {
+        let (height, width) = self.grid.size;
+        let size = (height / 2, width / 2);
+        let bottom = height - size.0;
+        let right = width - size.1;
+
+        [
+            self.grid.view(Rect::new((0, 0), size)),
+            self.grid.view(Rect::new((0, right), size)),
+            self.grid.view(Rect::new((bottom, 0), size)),
+            self.grid.view(Rect::new((bottom, right), size)),
+        ]
+    }
+}
+
+impl TorusGrid<usize> {
+    pub fn increment(&mut self, pos: (i64, i64)) {
+        *self.get_mut(pos) += 1;
+    }
+
+    /// Sums the counts in each of the four [`TorusGrid::quadrants`].
+    pub fn quadrant_counts(&self) -> [usize; 4] {
+        self.quadrants()
+            .map(|quadrant| quadrant.iter_positions().map(|(_, count)| *count).sum())
+    }
+}
+
+impl<T> Index<(i64, i64)> for TorusGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (i64, i64)) -> &Self::Output {
+        self.get(pos)
+    }
+}
+
+impl<T> IndexMut<(i64, i64)> for TorusGrid<T> {
+    fn index_mut(&mut self, pos: (i64, i64)) -> &mut Self::Output {
+        self.get_mut(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let grid = TorusGrid::new_default((7, 11), 0usize);
+
+        assert_eq!(grid.wrap((-1, 11)), (6, 0));
+        assert_eq!(grid.wrap((15, -23)), (1, 10));
+        assert_eq!(grid.offset((4, 2), (-3, 2), 5), (3, 1));
+        assert_eq!(grid.offset((0, 0), (1, 1), i64::MAX), (0, 7));
+    }
+
+    #[test]
+    fn test_quadrant_counts() {
+        let mut grid = TorusGrid::new_default((3, 3), 0usize);
+        grid.increment((0, 0));
+        grid.increment((-1, -1));
+        grid.increment((2, 2));
+        grid.increment((1, 1));
+        grid[(0, 2)] += 3;
+
+        assert_eq!(grid.quadrant_counts(), [1, 3, 0, 2]);
+    }
+}
@@ -1,19 +1,48 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    ops::{Index, IndexMut},
+};
 
-pub struct Vec2D<T> {
-    pub data: Vec<T>,
+use crate::{direction::Direction, errors::ParseGridError, point::Point};
+
+/// A rectangular region of a grid, as a top-left corner and a size.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rect {
+    pub origin: (usize, usize),
     pub size: (usize, usize),
 }
 
-impl Clone for Vec2D<char> {
-    fn clone(&self) -> Self {
+impl Rect {
+    pub fn new(origin: (usize, usize), size: (usize, usize)) -> Self {
+        Self { origin, size }
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        pos.0 >= self.origin.0
+            && pos.1 >= self.origin.1
+            && pos.0 - self.origin.0 < self.size.0
+            && pos.1 - self.origin.1 < self.size.1
+    }
+
+    /// Shrinks the rectangle so it fits inside a grid of `size`.
+    pub fn clamp(&self, size: (usize, usize)) -> Self {
+        let origin = (self.origin.0.min(size.0), self.origin.1.min(size.1));
         Self {
-            data: self.data.clone(),
-            size: self.size,
+            origin,
+            size: (
+                self.size.0.min(size.0 - origin.0),
+                self.size.1.min(size.1 - origin.1),
+            ),
         }
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Vec2D<T> {
+    pub data: Vec<T>,
+    pub size: (usize, usize),
+}
+
 impl<T> Vec2D<T> {
     pub fn new_default(size: (usize, usize), default: T) -> Self
     where
@@ -25,6 +54,75 @@ impl<T> Vec2D<T> {
         }
     }
 
+    pub fn from_fn<F>(size: (usize, usize), mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        let mut data = Vec::with_capacity(size.0 * size.1);
+        for i in 0..size.0 {
+            for j in 0..size.1 {
+                data.push(f((i, j)));
+            }
+        }
+        Self { data, size }
+    }
+
+    /// Parses a rectangular grid, mapping every character through `mapper`.
+    /// Blank lines at the end are ignored. Lines and columns in the returned
+    /// error are 1-based.
+    pub fn parse<F>(input: &str, mut mapper: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let input = input.trim_end_matches(['\n', '\r']);
+        let mut data = Vec::with_capacity(input.len());
+        let mut size = (0, 0);
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let mut width = 0;
+            for (j, c) in line.chars().enumerate() {
+                if i > 0 && j >= size.1 {
+                    return Err(ParseGridError::RaggedRow {
+                        line: i + 1,
+                        column: j + 1,
+                        expected: size.1,
+                        found: line.chars().count(),
+                    });
+                }
+                match mapper(c) {
+                    Some(value) => data.push(value),
+                    None => {
+                        return Err(ParseGridError::InvalidCell {
+                            line: i + 1,
+                            column: j + 1,
+                            value: c,
+                        })
+                    }
+                }
+                width += 1;
+            }
+
+            if i == 0 {
+                size.1 = width;
+            } else if width != size.1 {
+                return Err(ParseGridError::RaggedRow {
+                    line: i + 1,
+                    column: width + 1,
+                    expected: size.1,
+                    found: width,
+                });
+            }
+            size.0 += 1;
+        }
+
+        if size.0 == 0 || size.1 == 0 {
+            return Err(ParseGridError::Empty);
+        }
+
+        Ok(Self { data, size })
+    }
+
     pub fn get(&self, x: usize, y: usize) -> Option<&T> {
         if x >= self.size.0 || y >= self.size.1 {
             return None;
@@ -39,6 +137,26 @@ impl<T> Vec2D<T> {
         self.data[x * self.size.1 + y] = value;
     }
 
+    /// Like [`Vec2D::get`], but also accepts signed points. Negative or
+    /// out-of-bounds points return `None`.
+    pub fn get_point<U>(&self, point: Point<U>) -> Option<&T>
+    where
+        usize: TryFrom<U>,
+    {
+        let point = point.try_cast::<usize>()?;
+        self.get(point.x, point.y)
+    }
+
+    /// Like [`Vec2D::set`], ignoring negative or out-of-bounds points.
+    pub fn set_point<U>(&mut self, point: Point<U>, value: T)
+    where
+        usize: TryFrom<U>,
+    {
+        if let Some(point) = point.try_cast::<usize>() {
+            self.set(point.x, point.y, value);
+        }
+    }
+
     pub fn find_first(&self, value: T) -> Option<(usize, usize)>
     where
         T: PartialEq,
@@ -67,17 +185,316 @@ impl<T> Vec2D<T> {
         }
         result
     }
+
+    /// Yields every cell with its position, row by row.
+    pub fn iter_positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.size.1;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(idx, value)| ((idx / width, idx % width), value))
+    }
+
+    /// Yields the in-bounds orthogonal neighbours of `pos`.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(pos, &Direction::CARDINAL)
+    }
+
+    /// Yields the in-bounds orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(pos, &Direction::ALL)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: (usize, usize),
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        directions.iter().filter_map(move |dir| {
+            let next = dir.checked_add(pos)?;
+            self.get(next.0, next.1).map(|value| (next, value))
+        })
+    }
+
+    /// Walks from `start` (inclusive) in `direction` until leaving the grid.
+    pub fn walk(
+        &self,
+        start: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let mut next = Some(start);
+        std::iter::from_fn(move || {
+            let pos = next?;
+            let value = self.get(pos.0, pos.1)?;
+            next = direction.checked_add(pos);
+            Some((pos, value))
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects a zero width, so yield nothing for an empty grid.
+        self.data.chunks(self.size.1.max(1)).take(self.size.0)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.size.1).map(move |j| self.walk((0, j), Direction::Down).map(|(_, value)| value))
+    }
+
+    /// Yields every `\` diagonal followed by every `/` diagonal, each walked
+    /// from the top row down.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = self.size;
+        let down_right = (1..height)
+            .rev()
+            .map(|i| (i, 0))
+            .chain((0..width).map(|j| (0, j)))
+            .map(|start| (start, Direction::DownRight));
+        let down_left = (0..width)
+            .map(|j| (0, j))
+            .chain((1..height).map(move |i| (i, width.saturating_sub(1))))
+            .map(|start| (start, Direction::DownLeft));
+
+        down_right
+            .chain(down_left)
+            .map(move |(start, dir)| self.walk(start, dir).map(|(_, value)| value))
+    }
 }
 
-impl Debug for Vec2D<char> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.size.0 {
-            for j in 0..self.size.1 {
-                write!(f, "{}", self.data[i * self.size.1 + j])?;
+impl<T: Clone> Vec2D<T> {
+    pub fn transpose(&self) -> Self {
+        Self::from_fn((self.size.1, self.size.0), |(i, j)| self[(j, i)].clone())
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.size.0;
+        Self::from_fn((self.size.1, self.size.0), |(i, j)| {
+            self[(height - 1 - j, i)].clone()
+        })
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.size.1;
+        Self::from_fn((self.size.1, self.size.0), |(i, j)| {
+            self[(j, width - 1 - i)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_h(&self) -> Self {
+        let width = self.size.1;
+        Self::from_fn(self.size, |(i, j)| self[(i, width - 1 - j)].clone())
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_v(&self) -> Self {
+        let height = self.size.0;
+        Self::from_fn(self.size, |(i, j)| self[(height - 1 - i, j)].clone())
+    }
+
+    /// Copies out `rect`, clipped to the grid bounds.
+    pub fn crop(&self, rect: Rect) -> Self {
+        let rect = rect.clamp(self.size);
+        Self::from_fn(rect.size, |(i, j)| {
+            self[(rect.origin.0 + i, rect.origin.1 + j)].clone()
+        })
+    }
+
+    /// Surrounds the grid with a border of `n` cells set to `fill`.
+    pub fn pad(&self, n: usize, fill: T) -> Self {
+        let inner = Rect::new((n, n), self.size);
+        Self::from_fn((self.size.0 + 2 * n, self.size.1 + 2 * n), |(i, j)| {
+            if inner.contains((i, j)) {
+                self[(i - n, j - n)].clone()
+            } else {
+                fill.clone()
             }
-            writeln!(f)?;
+        })
+    }
+}
+
+impl<T> Vec2D<T> {
+    /// Replaces every cell with the `factor` cells returned by `mapper`,
+    /// placed side by side in the same row.
+    pub fn widen<U, F, I>(&self, factor: usize, mut mapper: F) -> Vec2D<U>
+    where
+        F: FnMut(&T) -> I,
+        I: IntoIterator<Item = U>,
+    {
+        let mut data = Vec::with_capacity(self.data.len() * factor);
+        for value in self.data.iter() {
+            let before = data.len();
+            data.extend(mapper(value));
+            assert_eq!(
+                data.len() - before,
+                factor,
+                "widen mapper must return exactly {} cells",
+                factor
+            );
+        }
+        Vec2D {
+            data,
+            size: (self.size.0, self.size.1 * factor),
+        }
+    }
+}
+
+impl<T> Vec2D<T> {
+    /// Borrows `rect`, clipped to the grid bounds, as a read-only sub-grid.
+    pub fn view(&self, rect: Rect) -> Vec2DView<'_, T> {
+        Vec2DView {
+            rect: rect.clamp(self.size),
+            grid: self,
+        }
+    }
+
+    /// Borrows `rect`, clipped to the grid bounds, as a mutable sub-grid.
+    pub fn view_mut(&mut self, rect: Rect) -> Vec2DViewMut<'_, T> {
+        Vec2DViewMut {
+            rect: rect.clamp(self.size),
+            grid: self,
         }
-        Ok(())
+    }
+}
+
+/// A rectangular window into a [`Vec2D`]. Positions are local to the window.
+pub struct Vec2DView<'a, T> {
+    grid: &'a Vec2D<T>,
+    rect: Rect,
+}
+
+impl<'a, T> Vec2DView<'a, T> {
+    pub fn size(&self) -> (usize, usize) {
+        self.rect.size
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Maps a local position back to the position in the parent grid.
+    pub fn to_parent(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (self.rect.origin.0 + x, self.rect.origin.1 + y)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.rect.size.0 || y >= self.rect.size.1 {
+            return None;
+        }
+        let (px, py) = self.to_parent((x, y));
+        self.grid.get(px, py)
+    }
+
+    /// Yields every cell of the window with its local position, row by row.
+    pub fn iter_positions(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> + '_ {
+        (0..self.rect.size.0).flat_map(move |i| {
+            (0..self.rect.size.1).filter_map(move |j| self.get(i, j).map(|value| ((i, j), value)))
+        })
+    }
+
+    pub fn find_first(&self, value: T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter_positions()
+            .find(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn find_all(&self, value: T) -> Vec<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter_positions()
+            .filter(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    pub fn to_vec2d(&self) -> Vec2D<T>
+    where
+        T: Clone,
+    {
+        self.grid.crop(self.rect)
+    }
+}
+
+/// A mutable rectangular window into a [`Vec2D`]. Positions are local to the
+/// window.
+pub struct Vec2DViewMut<'a, T> {
+    grid: &'a mut Vec2D<T>,
+    rect: Rect,
+}
+
+impl<T> Vec2DViewMut<'_, T> {
+    pub fn as_view(&self) -> Vec2DView<'_, T> {
+        Vec2DView {
+            grid: self.grid,
+            rect: self.rect,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.rect.size
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Maps a local position back to the position in the parent grid.
+    pub fn to_parent(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        (self.rect.origin.0 + x, self.rect.origin.1 + y)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.as_view().get(x, y)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.rect.size.0 || y >= self.rect.size.1 {
+            return None;
+        }
+        let (px, py) = self.to_parent((x, y));
+        Some(&mut self.grid[(px, py)])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        if let Some(cell) = self.get_mut(x, y) {
+            *cell = value;
+        }
+    }
+
+    /// Sets every cell of the window to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for i in 0..self.rect.size.0 {
+            for j in 0..self.rect.size.1 {
+                self.set(i, j, value.clone());
+            }
+        }
+    }
+
+    pub fn iter_positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let (rows, cols) = self.rect.size;
+        (0..rows).flat_map(move |i| {
+            (0..cols).filter_map(move |j| self.get(i, j).map(|value| ((i, j), value)))
+        })
+    }
+
+    pub fn find_first(&self, value: T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.as_view().find_first(value)
+    }
+
+    pub fn find_all(&self, value: T) -> Vec<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.as_view().find_all(value)
     }
 }
 
@@ -98,7 +515,49 @@ impl Vec2D<char> {
     }
 }
 
-impl Display for Vec2D<char> {
+impl<T> Index<(usize, usize)> for Vec2D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.size.0 && y < self.size.1,
+            "index ({}, {}) out of bounds for Vec2D of size {:?}",
+            x,
+            y,
+            self.size
+        );
+        &self.data[x * self.size.1 + y]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Vec2D<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.size.0 && y < self.size.1,
+            "index ({}, {}) out of bounds for Vec2D of size {:?}",
+            x,
+            y,
+            self.size
+        );
+        &mut self.data[x * self.size.1 + y]
+    }
+}
+
+impl<T> Index<Point<usize>> for Vec2D<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[(point.x, point.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Vec2D<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[(point.x, point.y)]
+    }
+}
+
+impl<T: Display> Display for Vec2D<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for i in 0..self.size.0 {
             for j in 0..self.size.1 {
@@ -110,14 +569,215 @@ impl Display for Vec2D<char> {
     }
 }
 
-impl Debug for Vec2D<i32> {
+impl<T: Debug> Debug for Vec2D<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for i in 0..self.size.0 {
             for j in 0..self.size.1 {
-                write!(f, "{} ", self.data[i * self.size.1 + j])?;
+                write!(f, "{:?} ", self.data[i * self.size.1 + j])?;
             }
             writeln!(f)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let mut grid = Vec2D::new_default((2, 3), 0u8);
+        grid[(1, 2)] = 7;
+
+        assert_eq!(grid[(1, 2)], 7);
+        assert_eq!(grid.get(1, 2), Some(&7));
+        assert_eq!(grid.data, vec![0, 0, 0, 0, 0, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid = Vec2D::new_default((2, 3), false);
+        let _ = grid[(0, 3)];
+    }
+
+    #[test]
+    fn test_clone_and_eq() {
+        let mut grid = Vec2D::new_default((2, 2), None::<usize>);
+        let copy = grid.clone();
+        assert_eq!(grid, copy);
+
+        grid.set(0, 1, Some(3));
+        assert_ne!(grid, copy);
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Vec2D::parse("012\r\n345\r\n", |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.size, (2, 3));
+        assert_eq!(grid[(1, 0)], 3);
+
+        let grid = Vec2D::parse("012\n345\n\n\r\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid.size, (2, 3));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Vec2D::parse("...\n....\n", Some),
+            Err(ParseGridError::RaggedRow {
+                line: 2,
+                column: 4,
+                expected: 3,
+                found: 4
+            })
+        );
+        assert_eq!(
+            Vec2D::parse("...\n.\n", Some),
+            Err(ParseGridError::RaggedRow {
+                line: 2,
+                column: 2,
+                expected: 3,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Vec2D::parse("12\n3x\n", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidCell {
+                line: 2,
+                column: 2,
+                value: 'x'
+            })
+        );
+        assert_eq!(
+            Vec2D::parse("...\n\n...\n", Some),
+            Err(ParseGridError::RaggedRow {
+                line: 2,
+                column: 1,
+                expected: 3,
+                found: 0
+            })
+        );
+        assert_eq!(Vec2D::parse("", Some), Err(ParseGridError::Empty));
+        assert_eq!(Vec2D::parse("\n\n", Some), Err(ParseGridError::Empty));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Vec2D::new_chars("abc\ndef\nghi");
+
+        let corner = grid.neighbors4((0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, vec![((1, 0), &'d'), ((0, 1), &'b')]);
+
+        let center = grid.neighbors8((1, 1)).map(|(_, c)| *c).collect::<String>();
+        assert_eq!(center, "bhdfacgi");
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Vec2D::new_chars("abc\ndef");
+
+        let rows = grid
+            .rows()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec!["abc", "def"]);
+
+        let cols = grid
+            .cols()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(cols, vec!["ad", "be", "cf"]);
+
+        let diagonals = grid
+            .diagonals()
+            .map(|d| d.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c", "a", "bd", "ce", "f"]);
+
+        let positions = grid.iter_positions().nth(4).unwrap();
+        assert_eq!(positions, ((1, 1), &'e'));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = Vec2D::new_chars("abc\ndef");
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.flip_h().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_v().to_string(), "def\nabc\n");
+    }
+
+    #[test]
+    fn test_crop_and_pad() {
+        let grid = Vec2D::new_chars("abc\ndef");
+
+        assert_eq!(grid.crop(Rect::new((0, 1), (2, 2))).to_string(), "bc\nef\n");
+        assert_eq!(grid.crop(Rect::new((1, 2), (5, 5))).to_string(), "f\n");
+        assert_eq!(grid.pad(1, '.').to_string(), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(grid.pad(2, '.').crop(Rect::new((2, 2), grid.size)), grid);
+    }
+
+    #[test]
+    fn test_widen() {
+        let grid = Vec2D::new_chars("#O\n@.");
+        let wide = grid.widen(2, |c| match c {
+            'O' => ['[', ']'],
+            '@' => ['@', '.'],
+            c => [*c, *c],
+        });
+
+        assert_eq!(wide.to_string(), "##[]\n@...\n");
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = Vec2D::new_chars("ab#\nc#d\n##e");
+        let view = grid.view(Rect::new((1, 1), (5, 5)));
+
+        assert_eq!(view.size(), (2, 2));
+        assert_eq!(view.get(0, 1), Some(&'d'));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.find_all('#'), vec![(0, 0), (1, 0)]);
+        assert_eq!(view.to_parent((1, 0)), (2, 1));
+        assert_eq!(view.to_vec2d().to_string(), "#d\n#e\n");
+    }
+
+    #[test]
+    fn test_view_mut() {
+        let mut grid = Vec2D::new_default((3, 3), '.');
+        let mut view = grid.view_mut(Rect::new((1, 0), (2, 2)));
+        view.fill('#');
+        view.set(1, 1, '@');
+
+        assert_eq!(view.find_first('@'), Some((1, 1)));
+        assert_eq!(grid.to_string(), "...\n##.\n#@.\n");
+    }
+
+    #[test]
+    fn test_points() {
+        let mut grid = Vec2D::new_chars("ab\ncd");
+        grid.set_point(Point::new(-1i64, 0), '#');
+        grid.set_point(Point::new(1i64, 0), '#');
+
+        assert_eq!(grid.get_point(Point::new(1i32, 1)), Some(&'d'));
+        assert_eq!(grid.get_point(Point::new(0i32, -1)), None);
+        assert_eq!(grid.get_point(Point::new(2usize, 0)), None);
+        assert_eq!(grid[Point::new(1, 0)], '#');
+    }
+
+    #[test]
+    fn test_fmt() {
+        let grid = Vec2D::new_chars("#.\n.#");
+        assert_eq!(grid.to_string(), "#.\n.#\n");
+
+        let grid = Vec2D::new_default((1, 2), true);
+        assert_eq!(format!("{:?}", grid), "true true \n");
+    }
+}
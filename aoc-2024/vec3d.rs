@@ -0,0 +1,166 @@
+use std::ops::{Index, IndexMut};
+
+use crate::direction::{checked_add_3d, Direction3D};
+
+/// A dense 3D grid stored in `x`, then `y`, then `z` order.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Vec3D<T> {
+    pub data: Vec<T>,
+    pub size: (usize, usize, usize),
+}
+
+impl<T> Vec3D<T> {
+    pub fn new_default(size: (usize, usize, usize), default: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![default; size.0 * size.1 * size.2],
+            size,
+        }
+    }
+
+    pub fn from_fn<F>(size: (usize, usize, usize), mut f: F) -> Self
+    where
+        F: FnMut((usize, usize, usize)) -> T,
+    {
+        let mut data = Vec::with_capacity(size.0 * size.1 * size.2);
+        for x in 0..size.0 {
+            for y in 0..size.1 {
+                for z in 0..size.2 {
+                    data.push(f((x, y, z)));
+                }
+            }
+        }
+        Self { data, size }
+    }
+
+    fn index_of(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        if x >= self.size.0 || y >= self.size.1 || z >= self.size.2 {
+            return None;
+        }
+        Some((x * self.size.1 + y) * self.size.2 + z)
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<&T> {
+        self.index_of(x, y, z).map(|idx| &self.data[idx])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: T) {
+        if let Some(idx) = self.index_of(x, y, z) {
+            self.data[idx] = value;
+        }
+    }
+
+    /// Yields every cell with its position.
+    pub fn iter_positions(&self) -> impl Iterator<Item = ((usize, usize, usize), &T)> {
+        let (_, height, depth) = self.size;
+        self.data.iter().enumerate().map(move |(idx, value)| {
+            let z = idx % depth;
+            let y = idx / depth % height;
+            let x = idx / depth / height;
+            ((x, y, z), value)
+        })
+    }
+
+    pub fn find_first(&self, value: T) -> Option<(usize, usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter_positions()
+            .find(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn find_all(&self, value: T) -> Vec<(usize, usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter_positions()
+            .filter(|(_, c)| **c == value)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Yields the in-bounds face neighbours of `pos`.
+    pub fn neighbors6(
+        &self,
+        pos: (usize, usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize, usize), &T)> {
+        Direction3D::ALL
+            .into_iter()
+            .map(<(isize, isize, isize)>::from)
+            .filter_map(move |offset| self.neighbor(pos, offset))
+    }
+
+    /// Yields the in-bounds face, edge and corner neighbours of `pos`.
+    pub fn neighbors26(
+        &self,
+        pos: (usize, usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize, usize), &T)> {
+        Direction3D::offsets26().filter_map(move |offset| self.neighbor(pos, offset))
+    }
+
+    fn neighbor(
+        &self,
+        pos: (usize, usize, usize),
+        offset: (isize, isize, isize),
+    ) -> Option<((usize, usize, usize), &T)> {
+        let next = checked_add_3d(pos, offset)?;
+        self.get(next.0, next.1, next.2).map(|value| (next, value))
+    }
+}
+
+impl<T> Index<(usize, usize, usize)> for Vec3D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &Self::Output {
+        let idx = self.index_of(x, y, z).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}, {}) out of bounds for Vec3D of size {:?}",
+                x, y, z, self.size
+            )
+        });
+        &self.data[idx]
+    }
+}
+
+impl<T> IndexMut<(usize, usize, usize)> for Vec3D<T> {
+    fn index_mut(&mut self, (x, y, z): (usize, usize, usize)) -> &mut Self::Output {
+        let idx = self.index_of(x, y, z).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}, {}) out of bounds for Vec3D of size {:?}",
+                x, y, z, self.size
+            )
+        });
+        &mut self.data[idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = Vec3D::new_default((2, 3, 4), 0);
+        grid.set(1, 2, 3, 5);
+        grid[(0, 1, 2)] = 7;
+        grid.set(2, 0, 0, 9);
+
+        assert_eq!(grid.get(1, 2, 3), Some(&5));
+        assert_eq!(grid.get(2, 0, 0), None);
+        assert_eq!(grid.find_all(7), vec![(0, 1, 2)]);
+        assert_eq!(grid.iter_positions().last(), Some(((1, 2, 3), &5)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Vec3D::from_fn((3, 3, 3), |(x, y, z)| x + y + z);
+
+        assert_eq!(grid.neighbors6((1, 1, 1)).count(), 6);
+        assert_eq!(grid.neighbors26((1, 1, 1)).count(), 26);
+        assert_eq!(grid.neighbors6((0, 0, 0)).count(), 3);
+        assert_eq!(grid.neighbors26((0, 0, 0)).count(), 7);
+    }
+}